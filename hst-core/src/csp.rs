@@ -76,6 +76,7 @@ where
 }
 
 #[doc(hidden)]
+#[allow(clippy::type_complexity)]
 #[derive(Clone, Eq, PartialEq)]
 pub struct CSPCursor<E>(
     Box<
@@ -117,6 +118,7 @@ where
 }
 
 #[doc(hidden)]
+#[allow(clippy::type_complexity)]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct CSPAlphabet<E>(
     Box<
@@ -143,6 +145,7 @@ where
 }
 
 #[doc(hidden)]
+#[allow(clippy::type_complexity)]
 pub struct CSPAlphabetIterator<E>(
     Box<CSPSigAlphabetIterator<
         <<<ExternalChoice<CSP<E>> as Process<E>>::Cursor as Cursor<E>>::Alphabet as IntoIterator>::IntoIter,
@@ -422,7 +425,7 @@ mod proptest_support {
                 // explicitly refer to Tau and Tick; those should only be created as part of
                 // the CSP operators.
                 (E::nameable_events(), inner.clone())
-                    .prop_map(|(initial, after)| prefix(initial.into(), after))
+                    .prop_map(|(initial, after)| prefix(initial, after))
             });
             basic
                 .prop_recursive(4, 64, 16, move |inner| {
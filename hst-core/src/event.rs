@@ -43,6 +43,12 @@ impl<E> EmptyAlphabet<E> {
     }
 }
 
+impl<E> Default for EmptyAlphabet<E> {
+    fn default() -> EmptyAlphabet<E> {
+        EmptyAlphabet::new()
+    }
+}
+
 impl<E> Alphabet<E> for EmptyAlphabet<E> {
     fn contains(&self, _event: &E) -> bool {
         false
@@ -134,7 +134,7 @@ where
 
 impl<E, C> InternalChoiceCursor<E, C> {
     /// Returns an iterator of the subcursors that are still activated.
-    fn activated_subcursors<'a>(&'a self) -> impl Iterator<Item = &C> + 'a {
+    fn activated_subcursors(&self) -> impl Iterator<Item = &C> {
         self.activated
             .iter()
             .zip(&self.subcursors)
@@ -218,6 +218,7 @@ where
 
 #[doc(hidden)]
 #[enum_derive(Iterator)]
+#[allow(clippy::type_complexity)]
 pub enum InternalChoiceAlphabetIterator<E, A>
 where
     A: IntoIterator<Item = E>,
@@ -99,7 +99,7 @@ where
         MaximalTraces(hashset! {vec![]})
     }
 
-    pub fn iter<'a>(&'a self) -> impl Iterator<Item = &'a Vec<E>> {
+    pub fn iter(&self) -> impl Iterator<Item = &Vec<E>> {
         self.0.iter()
     }
 }
@@ -29,7 +29,6 @@ use crate::process::Process;
 
 /// Constructs a new _sequential composition_ process `P ; Q`.  This process behaves like process
 /// `P` until it performs a ✔ event, after which is behaves like process `Q`.
-
 pub fn sequential_composition<P: From<SequentialComposition<P>>>(p: P, q: P) -> P {
     SequentialComposition(p, q).into()
 }
@@ -147,10 +146,8 @@ where
         }
 
         // If P can perform a ✔, then we can perform a τ and become Q after performing this event.
-        if *event == tau() {
-            if p.can_perform(&tick()) {
-                self.qs.push(Some(self.q_root.clone()));
-            }
+        if *event == tau() && p.can_perform(&tick()) {
+            self.qs.push(Some(self.q_root.clone()));
         }
 
         if p.can_perform(event) {
@@ -299,7 +296,7 @@ mod sequential_composition_tests {
         // For any trace of P, we need to replace a ✔ at the end with all possible traces of Q.
         let mut expected = MaximalTraces::new();
         for mut trace in maximal_finite_traces(p.root()) {
-            if trace.ends_with(&vec![tick()]) {
+            if trace.ends_with(&[tick()]) {
                 trace.pop();
                 expected.insert(trace.clone());
                 for suffix in maximal_finite_traces(q.root()) {
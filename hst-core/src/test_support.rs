@@ -75,7 +75,7 @@ impl Arbitrary for NumberedEvent {
 fn can_display_events() {
     assert_eq!(NumberedEvent(0).to_string(), "E₀");
     assert_eq!(NumberedEvent(10).to_string(), "E₁₀");
-    assert_eq!(NumberedEvent(01234).to_string(), "E₁₂₃₄");
+    assert_eq!(NumberedEvent(1234).to_string(), "E₁₂₃₄");
 }
 
 /// An event type that is useful in test cases.  It can be a NumberedEvent or any of the
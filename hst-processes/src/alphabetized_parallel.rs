@@ -0,0 +1,335 @@
+// -*- coding: utf-8 -*-
+// ------------------------------------------------------------------------------------------------
+// Copyright © 2020, HST authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License.  You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied.  See the License for the specific language governing permissions and
+// limitations under the License.
+// ------------------------------------------------------------------------------------------------
+
+//! Defines the alphabetized parallel (`[A || B]`) operator.
+
+use std::fmt::Debug;
+use std::fmt::Display;
+
+use crate::csp::CSP;
+use crate::event::EventSet;
+use crate::primitives::Tau;
+use crate::primitives::Tick;
+use crate::probabilistic_choice::map_distribution;
+use crate::probabilistic_choice::Distribution;
+
+#[derive(Clone, Eq, Hash, PartialEq)]
+pub struct AlphabetizedParallel<E, TauProof, TickProof> {
+    p: CSP<E, TauProof, TickProof>,
+    p_alphabet: E,
+    q_alphabet: E,
+    q: CSP<E, TauProof, TickProof>,
+}
+
+impl<E, TauProof, TickProof> Debug for AlphabetizedParallel<E, TauProof, TickProof>
+where
+    E: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("AlphabetizedParallel")
+            .field("p", &self.p)
+            .field("p_alphabet", &self.p_alphabet)
+            .field("q_alphabet", &self.q_alphabet)
+            .field("q", &self.q)
+            .finish()
+    }
+}
+
+impl<E, TauProof, TickProof> Display for AlphabetizedParallel<E, TauProof, TickProof>
+where
+    E: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{} [{} || {}] {}",
+            self.p, self.p_alphabet, self.q_alphabet, self.q
+        )
+    }
+}
+
+impl<E, TauProof, TickProof> AlphabetizedParallel<E, TauProof, TickProof> {
+    pub(crate) fn new(
+        p: CSP<E, TauProof, TickProof>,
+        p_alphabet: E,
+        q_alphabet: E,
+        q: CSP<E, TauProof, TickProof>,
+    ) -> AlphabetizedParallel<E, TauProof, TickProof> {
+        AlphabetizedParallel {
+            p,
+            p_alphabet,
+            q_alphabet,
+            q,
+        }
+    }
+}
+
+// Operational semantics for P [A || B] Q
+//
+//               P -a→ P'
+// 1)  ──────────────────────────────── a ∈ (A ∖ B) ∪ {τ}
+//      P [A || B] Q -a→ P' [A || B] Q
+//
+//               Q -a→ Q'
+// 2)  ──────────────────────────────── a ∈ (B ∖ A) ∪ {τ}
+//      P [A || B] Q -a→ P [A || B] Q'
+//
+//           P -a→ P'  Q -a→ Q'
+// 3)  ───────────────────────────────── a ∈ (A ∩ B) ∪ {✔}
+//      P [A || B] Q -a→ P' [A || B] Q'
+//
+// Note that neither process can perform any event outside of its own alphabet.
+
+impl<E, TauProof, TickProof> AlphabetizedParallel<E, TauProof, TickProof>
+where
+    E: Clone + EventSet + Tau<TauProof> + Tick<TickProof>,
+    TauProof: Clone,
+    TickProof: Clone,
+{
+    /// Returns the events that one side can perform without the other: anything in its alphabet
+    /// that isn't in the other's, plus τ, but never ✔.
+    fn independent(alphabet: &E, other_alphabet: &E) -> E {
+        let mut independent = alphabet.clone();
+        independent.subtract(other_alphabet);
+        independent.subtract(&E::tick());
+        independent.union(&E::tau());
+        independent
+    }
+
+    fn p_independent(&self) -> E {
+        Self::independent(&self.p_alphabet, &self.q_alphabet)
+    }
+
+    fn q_independent(&self) -> E {
+        Self::independent(&self.q_alphabet, &self.p_alphabet)
+    }
+
+    /// Returns the events that both sides must agree on: anything in both alphabets, plus ✔ (for
+    /// distributed termination), but never τ.
+    fn synchronized(&self) -> E {
+        let mut synchronized = self.p_alphabet.clone();
+        synchronized.intersect(&self.q_alphabet);
+        synchronized.union(&E::tick());
+        synchronized.subtract(&E::tau());
+        synchronized
+    }
+
+    pub(crate) fn distribution(&self) -> Option<Distribution<E, TauProof, TickProof>> {
+        if let Some(distribution) = self.p.distribution() {
+            return Some(map_distribution(distribution, |p| {
+                CSP::alphabetized_parallel(
+                    p,
+                    self.p_alphabet.clone(),
+                    self.q_alphabet.clone(),
+                    self.q.clone(),
+                )
+            }));
+        }
+        let distribution = self.q.distribution()?;
+        Some(map_distribution(distribution, |q| {
+            CSP::alphabetized_parallel(
+                self.p.clone(),
+                self.p_alphabet.clone(),
+                self.q_alphabet.clone(),
+                q,
+            )
+        }))
+    }
+
+    pub(crate) fn initials(&self) -> E {
+        let synchronized = self.synchronized();
+        let p_initials = self.p.initials();
+        let q_initials = self.q.initials();
+
+        // Each side can perform any event that's only in its own alphabet on its own.
+        let mut initials = p_initials.clone();
+        initials.intersect(&self.p_independent());
+        let mut q_only = q_initials.clone();
+        q_only.intersect(&self.q_independent());
+        initials.union(&q_only);
+
+        // Synchronized events need both sides to be able to perform them.
+        let mut both = p_initials;
+        both.intersect(&q_initials);
+        both.intersect(&synchronized);
+        initials.union(&both);
+        initials
+    }
+
+    pub(crate) fn transitions(
+        &self,
+        events: &E,
+    ) -> impl Iterator<Item = (E, CSP<E, TauProof, TickProof>)> + '_ {
+        let mut p_events = events.clone();
+        p_events.intersect(&self.p_independent());
+        let mut q_events = events.clone();
+        q_events.intersect(&self.q_independent());
+        let mut synchronized_events = events.clone();
+        synchronized_events.intersect(&self.synchronized());
+
+        // If P can perform an event that's only in its alphabet (or τ) leading to P', then the
+        // composition can too, leading to P' [A || B] Q.
+        let p_transitions = self
+            .p
+            .transitions(&p_events)
+            .map(move |(initials, p_after)| {
+                (
+                    initials,
+                    CSP::alphabetized_parallel(
+                        p_after,
+                        self.p_alphabet.clone(),
+                        self.q_alphabet.clone(),
+                        self.q.clone(),
+                    ),
+                )
+            });
+
+        // Same for Q.
+        let q_transitions = self
+            .q
+            .transitions(&q_events)
+            .map(move |(initials, q_after)| {
+                (
+                    initials,
+                    CSP::alphabetized_parallel(
+                        self.p.clone(),
+                        self.p_alphabet.clone(),
+                        self.q_alphabet.clone(),
+                        q_after,
+                    ),
+                )
+            });
+
+        // For synchronized events, we find the events that P can perform, and then ask Q which of
+        // _those_ events it can perform.  Whatever's left is what the composition can perform,
+        // with both sides advancing.
+        let synchronized_transitions =
+            self.p
+                .transitions(&synchronized_events)
+                .flat_map(move |(p_initials, p_after)| {
+                    self.q
+                        .transitions(&p_initials)
+                        .map(move |(initials, q_after)| {
+                            (
+                                initials,
+                                CSP::alphabetized_parallel(
+                                    p_after.clone(),
+                                    self.p_alphabet.clone(),
+                                    self.q_alphabet.clone(),
+                                    q_after,
+                                ),
+                            )
+                        })
+                });
+
+        p_transitions
+            .chain(q_transitions)
+            .chain(synchronized_transitions)
+    }
+}
+
+#[cfg(test)]
+mod alphabetized_parallel_tests {
+    use super::*;
+
+    use maplit::hashset;
+    use proptest_attr_macro::proptest;
+
+    use crate::csp::CSP;
+    use crate::maximal_traces::maximal_finite_traces;
+    use crate::test_support::NumberedEvent;
+    use crate::test_support::NumberedEvents;
+    use crate::test_support::TestEvents;
+
+    #[proptest]
+    fn check_alphabetized_parallel_initials(
+        p: CSP<TestEvents, _, _>,
+        p_alphabet: NumberedEvents,
+        q_alphabet: NumberedEvents,
+        q: CSP<TestEvents, _, _>,
+    ) {
+        let p_alphabet = TestEvents::from(p_alphabet);
+        let q_alphabet = TestEvents::from(q_alphabet);
+        let process = CSP::alphabetized_parallel(
+            p.clone(),
+            p_alphabet.clone(),
+            q_alphabet.clone(),
+            q.clone(),
+        );
+
+        let mut p_only = p_alphabet.clone();
+        p_only.subtract(&q_alphabet);
+        p_only.intersect(&p.initials());
+        let mut q_only = q_alphabet.clone();
+        q_only.subtract(&p_alphabet);
+        q_only.intersect(&q.initials());
+        let mut both = p_alphabet;
+        both.intersect(&q_alphabet);
+        both.union(&TestEvents::tick());
+        both.intersect(&p.initials());
+        both.intersect(&q.initials());
+
+        let mut expected = p_only;
+        expected.union(&q_only);
+        expected.union(&both);
+        assert_eq!(process.initials(), expected);
+    }
+
+    #[proptest]
+    fn check_alphabetized_parallel_traces(p: CSP<TestEvents, _, _>, q: CSP<TestEvents, _, _>) {
+        // If both processes can use every event, this is the same as synchronizing on every event.
+        let universe = TestEvents::from(NumberedEvents::universe());
+        let process =
+            CSP::alphabetized_parallel(p.clone(), universe.clone(), universe.clone(), q.clone());
+        assert_eq!(
+            maximal_finite_traces(&process),
+            maximal_finite_traces(&CSP::generalized_parallel(p, universe, q))
+        );
+    }
+
+    #[test]
+    fn check_alphabetized_parallel_synchronizes_on_shared_events() {
+        let a = TestEvents::from(NumberedEvent(0));
+        let b = TestEvents::from(NumberedEvent(1));
+        let c = TestEvents::from(NumberedEvent(2));
+        let mut ab = a.clone();
+        ab.union(&b);
+        let mut bc = b.clone();
+        bc.union(&c);
+        let process = CSP::alphabetized_parallel(
+            CSP::prefix(a.clone(), CSP::prefix(b.clone(), CSP::skip())),
+            ab,
+            bc,
+            CSP::prefix(b.clone(), CSP::prefix(c.clone(), CSP::skip())),
+        );
+        assert_eq!(
+            maximal_finite_traces(&process),
+            hashset! {vec![a, b, c, TestEvents::tick()]}
+        );
+    }
+
+    #[test]
+    fn check_alphabetized_parallel_blocks_events_outside_alphabet() {
+        let a = TestEvents::from(NumberedEvent(0));
+        let b = TestEvents::from(NumberedEvent(1));
+        let process = CSP::alphabetized_parallel(
+            CSP::prefix(b, CSP::skip()),
+            a,
+            TestEvents::empty(),
+            CSP::skip(),
+        );
+        assert_eq!(maximal_finite_traces(&process), hashset! {vec![]});
+    }
+}
@@ -17,21 +17,60 @@
 
 use std::fmt::Debug;
 use std::fmt::Display;
+use std::hash::Hash;
 use std::rc::Rc;
 
+use crate::alphabetized_parallel::AlphabetizedParallel;
 use crate::event::EventSet;
+use crate::exception::Exception;
 use crate::external_choice::ExternalChoice;
+use crate::generalized_parallel::GeneralizedParallel;
+use crate::hiding::Hiding;
+use crate::interleave::Interleave;
 use crate::internal_choice::InternalChoice;
+use crate::interrupt::Interrupt;
+use crate::linked_parallel::LinkedParallel;
+use crate::linked_parallel::Links;
+use crate::operator::Custom;
+use crate::operator::Operator;
 use crate::prefix::Prefix;
+use crate::prefix_choice::PrefixChoice;
+use crate::primitives::Chaos;
+use crate::primitives::Div;
+use crate::primitives::Run;
 use crate::primitives::Skip;
 use crate::primitives::Stop;
 use crate::primitives::Tau;
 use crate::primitives::Tick;
+use crate::primitives::Tock;
+use crate::prioritise::Prioritise;
+use crate::probabilistic_choice::Distribution;
+use crate::probabilistic_choice::ProbabilisticChoice;
+use crate::recursion::Recursion;
+use crate::renaming::Rename;
+use crate::renaming::Renaming;
+use crate::sequential_composition::ReplicatedSequentialComposition;
 use crate::sequential_composition::SequentialComposition;
+use crate::state_machine::StateMachine;
+use crate::state_machine::StepFunction;
+use crate::timeout::Timeout;
 
-#[derive(Clone, Eq, Hash, PartialEq)]
+// Processes are compared and hashed structurally.  Clippy's `mutable_key_type` lint complains when
+// we use them as hash keys, because a recursive process points at its recursion scope, which uses
+// `RefCell`s so that processes can be defined after they've been created.  But a recursive process
+// is compared and hashed using only the address of its scope and its ID within that scope, neither
+// of which can ever change, so it's safe to use processes as keys.
+#[derive(Eq, Hash, PartialEq)]
 pub struct CSP<E, TauProof, TickProof>(Rc<CSPInner<E, TauProof, TickProof>>);
 
+// Cloning a process only clones the pointer to it, so it doesn't matter whether the event or proof
+// types are cloneable.
+impl<E, TauProof, TickProof> Clone for CSP<E, TauProof, TickProof> {
+    fn clone(&self) -> Self {
+        CSP(self.0.clone())
+    }
+}
+
 impl<E, TauProof, TickProof> Display for CSP<E, TauProof, TickProof>
 where
     E: Display,
@@ -51,6 +90,55 @@ where
 }
 
 impl<E, TauProof, TickProof> CSP<E, TauProof, TickProof> {
+    /// Constructs a new _alphabetized parallel_ process `P [A || B] Q`.  This process runs `P` and
+    /// `Q` in parallel.  `P` can only perform events in `A`, and `Q` can only perform events in
+    /// `B`.  Both processes must synchronize on any event in `A ∩ B` (and on ✔, so that the
+    /// composition only terminates once both processes have), but they can perform any other
+    /// events in their alphabets independently of each other.
+    pub fn alphabetized_parallel(p: Self, p_alphabet: E, q_alphabet: E, q: Self) -> Self {
+        CSP(Rc::new(CSPInner::AlphabetizedParallel(
+            AlphabetizedParallel::new(p, p_alphabet, q_alphabet, q),
+        )))
+    }
+
+    /// Constructs a new _CHAOS_ process `CHAOS(A)`.  This process can perform any event in `A`,
+    /// but can also nondeterministically refuse any of them at any point.  It is the least
+    /// deterministic divergence-free process over `A`.  (τ and ✔ are never included.)
+    pub fn chaos(alphabet: E) -> Self {
+        CSP(Rc::new(CSPInner::Chaos(Chaos::new(alphabet))))
+    }
+
+    /// Constructs a new _deadline_ process `P ◀ n`, for use in tock-CSP.  This process behaves
+    /// like `P`, but it only allows `n` tock events to occur before `P` terminates.  If `P` hasn't
+    /// terminated by then, time cannot pass any further.  (This is a _timelock_, which
+    /// [`is_timelock_free`][crate::is_timelock_free] will report as a failure to meet the
+    /// deadline.)
+    pub fn deadline<TockProof>(p: Self, n: usize) -> Self
+    where
+        E: Tock<TockProof>,
+    {
+        // A process that allows up to n tocks to pass, and can terminate at any point.
+        let budget = (0..n).fold(CSP::skip(), |after, _| {
+            CSP::external_choice(CSP::prefix(E::tock(), after), CSP::skip())
+        });
+        CSP::generalized_parallel(p, E::tock(), budget)
+    }
+
+    /// Constructs a new _DIV_ process.  This is the process that performs τ forever, without ever
+    /// performing a visible event.
+    pub fn div() -> Self {
+        CSP(Rc::new(CSPInner::Div(Div::new())))
+    }
+
+    /// Constructs a new _exception_ process `P Θ_A Q`.  This process behaves like process `P`
+    /// until it performs an event in `A`, after which it behaves like process `Q`.  (τ and ✔ can
+    /// never be exceptions.)
+    pub fn exception(p: Self, exceptions: E, q: Self) -> Self {
+        CSP(Rc::new(CSPInner::Exception(Exception::new(
+            p, exceptions, q,
+        ))))
+    }
+
     /// Constructs a new _external choice_ process `P □ Q`.  This process behaves either like `P`
     /// _or_ `Q`, and the environment gets to choose — the process is willing to do either.
     pub fn external_choice(p: Self, q: Self) -> Self {
@@ -59,6 +147,39 @@ impl<E, TauProof, TickProof> CSP<E, TauProof, TickProof> {
         ))))
     }
 
+    /// Constructs a new process whose behavior is defined by a state machine, starting in
+    /// `initial_state`.  Whenever the process needs to perform some set of events, it calls `step`
+    /// with the current state and that set of events; `step` returns each subset of those events
+    /// that the machine can perform, along with the state that it moves to afterwards.  States
+    /// are compared for equality, so a machine that returns to an earlier state has a finite
+    /// state graph.
+    pub fn from_state_machine<S>(initial_state: S, step: StepFunction<S, E>) -> Self
+    where
+        S: Clone + Debug + Eq + Hash + 'static,
+        E: Clone + EventSet + 'static,
+        TauProof: 'static,
+        TickProof: 'static,
+    {
+        CSP::operator(StateMachine::new(initial_state, step))
+    }
+
+    /// Constructs a new _generalized parallel_ process `P [|A|] Q`.  This process runs `P` and `Q`
+    /// in parallel.  Both processes must synchronize on any event in `A` (and on ✔, so that the
+    /// composition only terminates once both processes have), but they can perform any other
+    /// events independently of each other.
+    pub fn generalized_parallel(p: Self, alphabet: E, q: Self) -> Self {
+        CSP(Rc::new(CSPInner::GeneralizedParallel(
+            GeneralizedParallel::new(p, alphabet, q),
+        )))
+    }
+
+    /// Constructs a new _hiding_ process `P \ A`.  This process behaves like `P`, except that any
+    /// event in `A` is turned into a τ, making it invisible to the environment.  (τ and ✔ can
+    /// never be hidden.)
+    pub fn hide(p: Self, hidden: E) -> Self {
+        CSP(Rc::new(CSPInner::Hiding(Hiding::new(p, hidden))))
+    }
+
     /// Constructs a new _external choice_ process `P ⊓ Q`.  This process behaves either like `P`
     /// _or_ `Q`, but the environment has no control over which one is chosen.
     pub fn internal_choice(p: Self, q: Self) -> Self {
@@ -67,12 +188,147 @@ impl<E, TauProof, TickProof> CSP<E, TauProof, TickProof> {
         ))))
     }
 
+    /// Constructs a new _interleaving_ process `P ||| Q`.  This process runs `P` and `Q` in
+    /// parallel, without synchronizing on any events.  The process terminates once both `P` and
+    /// `Q` have terminated.
+    pub fn interleave(p: Self, q: Self) -> Self {
+        CSP(Rc::new(CSPInner::Interleave(Interleave::new(vec![p, q]))))
+    }
+
+    /// Constructs a new _interrupt_ process `P △ Q`.  This process behaves like `P`, but can be
+    /// interrupted at any point by any of `Q`'s initial events, after which it behaves like `Q`.
+    /// Once `P` terminates, it can no longer be interrupted.
+    pub fn interrupt(p: Self, q: Self) -> Self {
+        CSP(Rc::new(CSPInner::Interrupt(Interrupt::new(p, q))))
+    }
+
+    /// Constructs a new _linked parallel_ process `P [a ↔ b] Q`.  Each link is a pair of event
+    /// sets `a` and `b`; whenever `P` performs an event in `a` and `Q` performs an event in `b`
+    /// that `renaming` relates to it, the two processes synchronize, and the shared event is
+    /// hidden.  All other events (including `P`'s events in `b` and `Q`'s events in `a`) are
+    /// interleaved.  The process terminates once both `P` and `Q` have terminated.
+    pub fn linked_parallel<I, R>(p: Self, links: I, renaming: R, q: Self) -> Self
+    where
+        I: IntoIterator<Item = (E, E)>,
+        R: Renaming<E> + 'static,
+    {
+        let links = Links::new(links.into_iter().collect(), Box::new(renaming));
+        CSP::shared_linked_parallel(p, Rc::new(links), q)
+    }
+
+    /// Constructs a new _linked parallel_ process that shares an existing set of links.  All of
+    /// the states of a linked parallel process must share the same links, since that's how we
+    /// tell that they use the same renaming.
+    pub(crate) fn shared_linked_parallel(p: Self, links: Rc<Links<E>>, q: Self) -> Self {
+        CSP(Rc::new(CSPInner::LinkedParallel(LinkedParallel::new(
+            p, links, q,
+        ))))
+    }
+
+    /// Constructs a new process from an [`Operator`][crate::Operator] that's defined outside of
+    /// this crate.  The process behaves exactly as the operator's `initials` and `transitions`
+    /// methods describe.
+    pub fn operator<O>(operator: O) -> Self
+    where
+        O: Operator<E, TauProof, TickProof>,
+    {
+        CSP(Rc::new(CSPInner::Custom(Custom::new(operator))))
+    }
+
     /// Constructs a new _prefix_ process `{a} → P`.  This process performs any event in `a` and
     /// then behaves like process `P`.
     pub fn prefix(initials: E, after: Self) -> Self {
         CSP(Rc::new(CSPInner::Prefix(Prefix::new(initials, after))))
     }
 
+    /// Constructs a new _prefix choice_ process `?x:A → P(x)`.  This process performs any event
+    /// `x` in `A` and then behaves like the process `P(x)` that the continuation returns for it.
+    /// The continuation is called separately for each individual event that the process performs.
+    pub fn prefix_choice<F>(initials: E, continuation: F) -> Self
+    where
+        E: Clone + IntoIterator<Item = E> + 'static,
+        F: Fn(&E) -> Self + 'static,
+        TauProof: 'static,
+        TickProof: 'static,
+    {
+        CSP::partitioned_prefix_choice(initials, move |events: &E| {
+            events
+                .clone()
+                .into_iter()
+                .map(|event| {
+                    let after = continuation(&event);
+                    (event, after)
+                })
+                .collect()
+        })
+    }
+
+    /// Constructs a new _prefix choice_ process `?x:A → P(x)`, where the continuation decides how
+    /// to group events together.  Whenever the process needs to perform some subset of `A`, the
+    /// continuation must split that subset into disjoint equivalence classes, and return the
+    /// process that each class leads to.  This lets you use prefix choice with event sets that
+    /// can't be (or are too large to be) split into individual events.
+    pub fn partitioned_prefix_choice<F>(initials: E, continuation: F) -> Self
+    where
+        F: Fn(&E) -> Vec<(E, Self)> + 'static,
+    {
+        CSP(Rc::new(CSPInner::PrefixChoice(PrefixChoice::new(
+            initials,
+            Rc::new(continuation),
+        ))))
+    }
+
+    /// Constructs a new _priority_ process `prioritise(P, ⟨A₁, A₂, …⟩)`.  This process behaves
+    /// like `P`, except that an event in `Aₖ` can only happen if `P` can't perform any event in
+    /// an earlier set `A₁, …, Aₖ₋₁`.  τ and ✔ are treated as if they were in `A₁`.  Events that
+    /// aren't in any of the sets are not affected by priority.
+    pub fn prioritise<I>(p: Self, priorities: I) -> Self
+    where
+        I: IntoIterator<Item = E>,
+    {
+        CSP::shared_prioritise(p, Rc::new(priorities.into_iter().collect()))
+    }
+
+    /// Constructs a new _priority_ process that shares an existing list of priorities.
+    pub(crate) fn shared_prioritise(p: Self, priorities: Rc<Vec<E>>) -> Self {
+        CSP(Rc::new(CSPInner::Prioritise(Prioritise::new(
+            p, priorities,
+        ))))
+    }
+
+    /// Constructs a new _probabilistic choice_ process `P [p]+ Q`.  This process behaves like `P`
+    /// with probability `p`, and like `Q` with probability `1-p`.  Analyses that don't know about
+    /// probabilities treat this like an internal choice.
+    ///
+    /// Panics if `p` is not between 0 and 1.
+    pub fn probabilistic_choice(p: Self, probability: f64, q: Self) -> Self {
+        CSP(Rc::new(CSPInner::ProbabilisticChoice(
+            ProbabilisticChoice::new(p, probability, q),
+        )))
+    }
+
+    /// Wraps a reference to a named process from a [`RecursionScope`][crate::RecursionScope].
+    pub(crate) fn recursion(recursion: Recursion<E, TauProof, TickProof>) -> Self {
+        CSP(Rc::new(CSPInner::Recursion(recursion)))
+    }
+
+    /// Constructs a new _renaming_ process `P[[R]]`.  This process behaves like `P`, except that
+    /// whenever `P` would perform an event `a`, this process instead performs any event `b` such
+    /// that `a R b`.  (τ and ✔ are never renamed.)
+    pub fn rename<R>(p: Self, renaming: R) -> Self
+    where
+        R: Renaming<E> + 'static,
+    {
+        CSP::shared_rename(p, Rc::new(renaming))
+    }
+
+    /// Constructs a new _renaming_ process that shares an existing renaming relation.  All of the
+    /// states of a renamed process must share the same relation, since that's how we tell that
+    /// they use the same renaming.
+    pub(crate) fn shared_rename(p: Self, renaming: Rc<dyn Renaming<E>>) -> Self {
+        CSP(Rc::new(CSPInner::Rename(Rename::new(p, renaming))))
+    }
+
     /// Constructs a new _replicated external choice_ process `□ Ps` over a non-empty collection of
     /// processes.  The process behaves like one of the processes in the set, but the environment
     /// has no control over which one is chosen.
@@ -85,6 +341,30 @@ impl<E, TauProof, TickProof> CSP<E, TauProof, TickProof> {
         ))))
     }
 
+    /// Constructs a new _replicated external choice_ process `□ x:A @ P(x)`, which chooses
+    /// between the processes that `p` returns for each individual event in `A`.  If `A` is empty,
+    /// the process behaves like _Stop_.
+    pub fn replicated_external_choice_over<F>(indices: E, p: F) -> Self
+    where
+        E: IntoIterator<Item = E>,
+        F: FnMut(&E) -> Self,
+    {
+        CSP::replicated_external_choice(indices.into_iter().map(each_index(p)))
+    }
+
+    /// Constructs a new _replicated external choice_ process `□ x:A @ P(x)`, where `partition`
+    /// splits `A` into disjoint equivalence classes and returns the process for each class.
+    /// Every index in a class must lead to the same process, so the choice only needs to consider
+    /// each class once.  This lets you replicate over event sets that can't be (or are too large
+    /// to be) split into individual events.
+    pub fn partitioned_replicated_external_choice<F>(indices: E, partition: F) -> Self
+    where
+        E: EventSet,
+        F: FnOnce(&E) -> Vec<(E, Self)>,
+    {
+        CSP::replicated_external_choice(partition_indices(&indices, partition))
+    }
+
     /// Constructs a new _replicated internal choice_ process `⊓ Ps` over a non-empty collection of
     /// processes.  The process behaves like one of the processes in the set, but the environment
     /// has no control over which one is chosen.
@@ -99,6 +379,83 @@ impl<E, TauProof, TickProof> CSP<E, TauProof, TickProof> {
         ))))
     }
 
+    /// Constructs a new _replicated internal choice_ process `⊓ x:A @ P(x)`, which chooses
+    /// between the processes that `p` returns for each individual event in `A`.
+    ///
+    /// Panics if `A` is empty.
+    pub fn replicated_internal_choice_over<F>(indices: E, p: F) -> Self
+    where
+        E: IntoIterator<Item = E>,
+        F: FnMut(&E) -> Self,
+    {
+        CSP::replicated_internal_choice(indices.into_iter().map(each_index(p)))
+    }
+
+    /// Constructs a new _replicated internal choice_ process `⊓ x:A @ P(x)`, where `partition`
+    /// splits `A` into disjoint equivalence classes and returns the process for each class.
+    /// Every index in a class must lead to the same process, so the choice only needs to consider
+    /// each class once.  This lets you replicate over event sets that can't be (or are too large
+    /// to be) split into individual events.
+    ///
+    /// Panics if `A` is empty.
+    pub fn partitioned_replicated_internal_choice<F>(indices: E, partition: F) -> Self
+    where
+        E: EventSet,
+        F: FnOnce(&E) -> Vec<(E, Self)>,
+    {
+        CSP::replicated_internal_choice(partition_indices(&indices, partition))
+    }
+
+    /// Constructs a new _replicated interleaving_ process `||| Ps` over a collection of processes.
+    /// The processes all run in parallel, without synchronizing on any events.  The process
+    /// terminates once all of the processes have terminated; if `ps` is empty, it behaves like
+    /// _Skip_.
+    pub fn replicated_interleave<I>(ps: I) -> Self
+    where
+        I: IntoIterator<Item = Self>,
+    {
+        CSP(Rc::new(CSPInner::Interleave(Interleave::new(
+            ps.into_iter().collect(),
+        ))))
+    }
+
+    /// Constructs a new _replicated sequential composition_ process `; i:⟨Ps⟩ @ P(i)` over an
+    /// ordered sequence of processes.  This process runs each process in order, moving on to the
+    /// next process each time the current one performs a ✔ event.  The ✔ of the last process is
+    /// visible, just as in `P ; Q`.  If `ps` is empty, it behaves like _Skip_.
+    pub fn replicated_sequential_composition<I>(ps: I) -> Self
+    where
+        I: IntoIterator<Item = Self>,
+    {
+        let ps: Rc<[Self]> = ps.into_iter().collect();
+        match ps.first() {
+            None => CSP::skip(),
+            Some(first) => CSP::continue_sequential_composition(first.clone(), ps.clone(), 1),
+        }
+    }
+
+    /// Constructs a new _replicated sequential composition_ process that is currently running
+    /// `current`, and will then run each process in `ps` starting at index `next`.  Once there
+    /// aren't any more processes to run afterwards, we just return `current` itself.
+    pub(crate) fn continue_sequential_composition(
+        current: Self,
+        ps: Rc<[Self]>,
+        next: usize,
+    ) -> Self {
+        if next >= ps.len() {
+            return current;
+        }
+        CSP(Rc::new(CSPInner::ReplicatedSequentialComposition(
+            ReplicatedSequentialComposition::new(current, ps, next),
+        )))
+    }
+
+    /// Constructs a new _RUN_ process `RUN(A)`.  This process can always perform any event in
+    /// `A`.  (τ and ✔ are never included.)
+    pub fn run(alphabet: E) -> Self {
+        CSP(Rc::new(CSPInner::Run(Run::new(alphabet))))
+    }
+
     /// Constructs a new _sequential composition_ process `P ; Q`.  This process behaves like
     /// process `P` until it performs a ✔ event, after which is behaves like process `Q`.
     pub fn sequential_composition(p: Self, q: Self) -> Self {
@@ -119,6 +476,32 @@ impl<E, TauProof, TickProof> CSP<E, TauProof, TickProof> {
     pub fn stop() -> Self {
         CSP(Rc::new(CSPInner::Stop(Stop::new())))
     }
+
+    /// Constructs a new _timeout_ (or _sliding choice_) process `P ▷ Q`.  This process offers the
+    /// same events as `P`, but can also decide at any point (via a τ event) to stop waiting and
+    /// behave like `Q` instead.
+    pub fn timeout(p: Self, q: Self) -> Self {
+        CSP(Rc::new(CSPInner::Timeout(Timeout::new(p, q))))
+    }
+
+    /// Constructs a new _urgent_ process, for use in tock-CSP.  This process behaves like `P`,
+    /// except that time cannot pass (via a tock event) while `P` is able to perform any of the
+    /// events in `urgent`.  (Just like in `prioritise`, τ and ✔ are always urgent.)
+    pub fn urgent<TockProof>(p: Self, urgent: E) -> Self
+    where
+        E: Tock<TockProof>,
+    {
+        CSP::prioritise(p, vec![urgent, E::tock()])
+    }
+
+    /// Constructs a new _wait_ process `WAIT n`, for use in tock-CSP.  This process lets `n`
+    /// tock events pass, and then terminates.
+    pub fn wait<TockProof>(n: usize) -> Self
+    where
+        E: Tock<TockProof>,
+    {
+        (0..n).fold(CSP::skip(), |after, _| CSP::prefix(E::tock(), after))
+    }
 }
 
 impl<E, TauProof, TickProof> CSP<E, TauProof, TickProof>
@@ -137,16 +520,97 @@ where
     ) -> impl Iterator<Item = (E, CSP<E, TauProof, TickProof>)> + '_ {
         self.0.transitions(events)
     }
+
+    /// If this process is a probabilistic choice, or contains one that is currently running (like
+    /// `P` in `P ||| Q`), returns the probability distribution over the processes that it can
+    /// become.  Each of the process's τ transitions that resolves the choice leads to one of
+    /// these processes.  Returns `None` for all other processes.
+    pub fn distribution(&self) -> Option<Distribution<E, TauProof, TickProof>> {
+        match &*self.0 {
+            CSPInner::AlphabetizedParallel(this) => this.distribution(),
+            CSPInner::Custom(this) => this.distribution(),
+            CSPInner::Exception(this) => this.distribution(),
+            CSPInner::ExternalChoice(this) => this.distribution(),
+            CSPInner::GeneralizedParallel(this) => this.distribution(),
+            CSPInner::Hiding(this) => this.distribution(),
+            CSPInner::Interleave(this) => this.distribution(),
+            CSPInner::Interrupt(this) => this.distribution(),
+            CSPInner::LinkedParallel(this) => this.distribution(),
+            CSPInner::Prioritise(this) => this.distribution(),
+            CSPInner::ProbabilisticChoice(this) => Some(this.distribution()),
+            CSPInner::Recursion(this) => this.distribution(),
+            CSPInner::Rename(this) => this.distribution(),
+            CSPInner::ReplicatedSequentialComposition(this) => this.distribution(),
+            CSPInner::SequentialComposition(this) => this.distribution(),
+            CSPInner::Timeout(this) => this.distribution(),
+            // None of these have an operand that's currently running.
+            CSPInner::Chaos(_)
+            | CSPInner::Div(_)
+            | CSPInner::InternalChoice(_)
+            | CSPInner::Prefix(_)
+            | CSPInner::PrefixChoice(_)
+            | CSPInner::Run(_)
+            | CSPInner::Skip(_)
+            | CSPInner::Stop(_) => None,
+        }
+    }
+}
+
+/// Adapts a function that takes in a single index into one that takes ownership of it, so that we
+/// can map it over the individual events in an event set.
+fn each_index<E, F, P>(mut p: F) -> impl FnMut(E) -> P
+where
+    F: FnMut(&E) -> P,
+{
+    move |index| p(&index)
+}
+
+/// Calls `partition` to split a set of indices into equivalence classes, and returns the process
+/// for each (non-empty) class.
+fn partition_indices<E, F, P>(indices: &E, partition: F) -> Vec<P>
+where
+    E: EventSet,
+    F: FnOnce(&E) -> Vec<(E, P)>,
+{
+    partition(indices)
+        .into_iter()
+        .filter_map(|(mut class, p)| {
+            class.intersect(indices);
+            if class.is_empty() {
+                None
+            } else {
+                Some(p)
+            }
+        })
+        .collect()
 }
 
 #[derive(Eq, Hash, PartialEq)]
 enum CSPInner<E, TauProof, TickProof> {
+    AlphabetizedParallel(AlphabetizedParallel<E, TauProof, TickProof>),
+    Chaos(Chaos<E>),
+    Custom(Custom<E, TauProof, TickProof>),
+    Div(Div<E, TauProof>),
+    Exception(Exception<E, TauProof, TickProof>),
     ExternalChoice(ExternalChoice<E, TauProof, TickProof>),
+    GeneralizedParallel(GeneralizedParallel<E, TauProof, TickProof>),
+    Hiding(Hiding<E, TauProof, TickProof>),
+    Interleave(Interleave<E, TauProof, TickProof>),
     InternalChoice(InternalChoice<E, TauProof, TickProof>),
+    Interrupt(Interrupt<E, TauProof, TickProof>),
+    LinkedParallel(LinkedParallel<E, TauProof, TickProof>),
     Prefix(Prefix<E, TauProof, TickProof>),
+    PrefixChoice(PrefixChoice<E, TauProof, TickProof>),
+    Prioritise(Prioritise<E, TauProof, TickProof>),
+    ProbabilisticChoice(ProbabilisticChoice<E, TauProof, TickProof>),
+    Recursion(Recursion<E, TauProof, TickProof>),
+    Rename(Rename<E, TauProof, TickProof>),
+    ReplicatedSequentialComposition(ReplicatedSequentialComposition<E, TauProof, TickProof>),
+    Run(Run<E>),
     SequentialComposition(SequentialComposition<E, TauProof, TickProof>),
     Skip(Skip<E, TickProof>),
     Stop(Stop<E>),
+    Timeout(Timeout<E, TauProof, TickProof>),
 }
 
 impl<E, TauProof, TickProof> Display for CSPInner<E, TauProof, TickProof>
@@ -155,12 +619,30 @@ where
 {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
+            CSPInner::AlphabetizedParallel(this) => (this as &dyn Display).fmt(f),
+            CSPInner::Chaos(this) => (this as &dyn Display).fmt(f),
+            CSPInner::Custom(this) => (this as &dyn Display).fmt(f),
+            CSPInner::Div(this) => (this as &dyn Display).fmt(f),
+            CSPInner::Exception(this) => (this as &dyn Display).fmt(f),
             CSPInner::ExternalChoice(this) => (this as &dyn Display).fmt(f),
+            CSPInner::GeneralizedParallel(this) => (this as &dyn Display).fmt(f),
+            CSPInner::Hiding(this) => (this as &dyn Display).fmt(f),
+            CSPInner::Interleave(this) => (this as &dyn Display).fmt(f),
             CSPInner::InternalChoice(this) => (this as &dyn Display).fmt(f),
+            CSPInner::Interrupt(this) => (this as &dyn Display).fmt(f),
+            CSPInner::LinkedParallel(this) => (this as &dyn Display).fmt(f),
             CSPInner::Prefix(this) => (this as &dyn Display).fmt(f),
+            CSPInner::PrefixChoice(this) => (this as &dyn Display).fmt(f),
+            CSPInner::Prioritise(this) => (this as &dyn Display).fmt(f),
+            CSPInner::ProbabilisticChoice(this) => (this as &dyn Display).fmt(f),
+            CSPInner::Recursion(this) => (this as &dyn Display).fmt(f),
+            CSPInner::Rename(this) => (this as &dyn Display).fmt(f),
+            CSPInner::ReplicatedSequentialComposition(this) => (this as &dyn Display).fmt(f),
+            CSPInner::Run(this) => (this as &dyn Display).fmt(f),
             CSPInner::SequentialComposition(this) => (this as &dyn Display).fmt(f),
             CSPInner::Skip(this) => (this as &dyn Display).fmt(f),
             CSPInner::Stop(this) => (this as &dyn Display).fmt(f),
+            CSPInner::Timeout(this) => (this as &dyn Display).fmt(f),
         }
     }
 }
@@ -171,12 +653,30 @@ where
 {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
+            CSPInner::AlphabetizedParallel(this) => (this as &dyn Debug).fmt(f),
+            CSPInner::Chaos(this) => (this as &dyn Debug).fmt(f),
+            CSPInner::Custom(this) => (this as &dyn Debug).fmt(f),
+            CSPInner::Div(this) => (this as &dyn Debug).fmt(f),
+            CSPInner::Exception(this) => (this as &dyn Debug).fmt(f),
             CSPInner::ExternalChoice(this) => (this as &dyn Debug).fmt(f),
+            CSPInner::GeneralizedParallel(this) => (this as &dyn Debug).fmt(f),
+            CSPInner::Hiding(this) => (this as &dyn Debug).fmt(f),
+            CSPInner::Interleave(this) => (this as &dyn Debug).fmt(f),
             CSPInner::InternalChoice(this) => (this as &dyn Debug).fmt(f),
+            CSPInner::Interrupt(this) => (this as &dyn Debug).fmt(f),
+            CSPInner::LinkedParallel(this) => (this as &dyn Debug).fmt(f),
             CSPInner::Prefix(this) => (this as &dyn Debug).fmt(f),
+            CSPInner::PrefixChoice(this) => (this as &dyn Debug).fmt(f),
+            CSPInner::Prioritise(this) => (this as &dyn Debug).fmt(f),
+            CSPInner::ProbabilisticChoice(this) => (this as &dyn Debug).fmt(f),
+            CSPInner::Recursion(this) => (this as &dyn Debug).fmt(f),
+            CSPInner::Rename(this) => (this as &dyn Debug).fmt(f),
+            CSPInner::ReplicatedSequentialComposition(this) => (this as &dyn Debug).fmt(f),
+            CSPInner::Run(this) => (this as &dyn Debug).fmt(f),
             CSPInner::SequentialComposition(this) => (this as &dyn Debug).fmt(f),
             CSPInner::Skip(this) => (this as &dyn Debug).fmt(f),
             CSPInner::Stop(this) => (this as &dyn Debug).fmt(f),
+            CSPInner::Timeout(this) => (this as &dyn Debug).fmt(f),
         }
     }
 }
@@ -189,12 +689,30 @@ where
 {
     fn initials(&self) -> E {
         match self {
+            CSPInner::AlphabetizedParallel(this) => this.initials(),
+            CSPInner::Chaos(this) => this.initials(),
+            CSPInner::Custom(this) => this.initials(),
+            CSPInner::Div(this) => this.initials(),
+            CSPInner::Exception(this) => this.initials(),
             CSPInner::ExternalChoice(this) => this.initials(),
+            CSPInner::GeneralizedParallel(this) => this.initials(),
+            CSPInner::Hiding(this) => this.initials(),
+            CSPInner::Interleave(this) => this.initials(),
             CSPInner::InternalChoice(this) => this.initials(),
+            CSPInner::Interrupt(this) => this.initials(),
+            CSPInner::LinkedParallel(this) => this.initials(),
             CSPInner::Prefix(this) => this.initials(),
+            CSPInner::PrefixChoice(this) => this.initials(),
+            CSPInner::Prioritise(this) => this.initials(),
+            CSPInner::ProbabilisticChoice(this) => this.initials(),
+            CSPInner::Recursion(this) => this.initials(),
+            CSPInner::Rename(this) => this.initials(),
+            CSPInner::ReplicatedSequentialComposition(this) => this.initials(),
+            CSPInner::Run(this) => this.initials(),
             CSPInner::SequentialComposition(this) => this.initials(),
             CSPInner::Skip(this) => this.initials(),
             CSPInner::Stop(this) => this.initials(),
+            CSPInner::Timeout(this) => this.initials(),
         }
     }
 
@@ -203,12 +721,30 @@ where
         events: &E,
     ) -> Box<dyn Iterator<Item = (E, CSP<E, TauProof, TickProof>)> + '_> {
         match self {
+            CSPInner::AlphabetizedParallel(this) => Box::new(this.transitions(events)),
+            CSPInner::Chaos(this) => Box::new(this.transitions(events)),
+            CSPInner::Custom(this) => Box::new(this.transitions(events)),
+            CSPInner::Div(this) => Box::new(this.transitions(events)),
+            CSPInner::Exception(this) => Box::new(this.transitions(events)),
             CSPInner::ExternalChoice(this) => Box::new(this.transitions(events)),
+            CSPInner::GeneralizedParallel(this) => Box::new(this.transitions(events)),
+            CSPInner::Hiding(this) => Box::new(this.transitions(events)),
+            CSPInner::Interleave(this) => Box::new(this.transitions(events)),
             CSPInner::InternalChoice(this) => Box::new(this.transitions(events)),
+            CSPInner::Interrupt(this) => Box::new(this.transitions(events)),
+            CSPInner::LinkedParallel(this) => Box::new(this.transitions(events)),
             CSPInner::Prefix(this) => Box::new(this.transitions(events)),
+            CSPInner::PrefixChoice(this) => Box::new(this.transitions(events)),
+            CSPInner::Prioritise(this) => Box::new(this.transitions(events)),
+            CSPInner::ProbabilisticChoice(this) => Box::new(this.transitions(events)),
+            CSPInner::Recursion(this) => Box::new(this.transitions(events)),
+            CSPInner::Rename(this) => Box::new(this.transitions(events)),
+            CSPInner::ReplicatedSequentialComposition(this) => Box::new(this.transitions(events)),
+            CSPInner::Run(this) => Box::new(this.transitions(events)),
             CSPInner::SequentialComposition(this) => Box::new(this.transitions(events)),
             CSPInner::Skip(this) => Box::new(this.transitions(events)),
             CSPInner::Stop(this) => Box::new(this.transitions(events)),
+            CSPInner::Timeout(this) => Box::new(this.transitions(events)),
         }
     }
 }
@@ -257,7 +793,7 @@ mod proptest_support {
                 // explicitly refer to Tau and Tick; those should only be created as part of
                 // the CSP operators.
                 (E::nameable_events(), inner.clone())
-                    .prop_map(|(initials, after)| CSP::prefix(initials.into(), after))
+                    .prop_map(|(initials, after)| CSP::prefix(initials, after))
             });
             basic.boxed()
         }
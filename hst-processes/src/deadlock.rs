@@ -0,0 +1,130 @@
+// -*- coding: utf-8 -*-
+// ------------------------------------------------------------------------------------------------
+// Copyright © 2020, HST authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License.  You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied.  See the License for the specific language governing permissions and
+// limitations under the License.
+// ------------------------------------------------------------------------------------------------
+//! Checks whether a process can deadlock.
+
+use std::hash::Hash;
+
+use crate::csp::CSP;
+use crate::event::EventSet;
+use crate::primitives::Tau;
+use crate::primitives::Tick;
+use crate::refinement::is_stable;
+use crate::search::Search;
+
+/// Checks whether a process is deadlock free — that is, whether it can never reach a stable state
+/// where it can't perform any events.  A process that has terminated (by performing ✔) hasn't
+/// deadlocked, even though it can't perform any more events.  If the process can deadlock,
+/// returns the shortest trace that leads to a deadlock.
+pub fn is_deadlock_free<E, TauProof, TickProof>(
+    process: &CSP<E, TauProof, TickProof>,
+) -> Result<(), Vec<E>>
+where
+    E: Clone + Eq + EventSet + Hash + Tau<TauProof> + Tick<TickProof>,
+    TauProof: Clone + Eq + Hash,
+    TickProof: Clone + Eq + Hash,
+{
+    let mut search = Search::new(process.clone());
+    while let Some(index) = search.next() {
+        let process = search.state(index).clone();
+        let initials = process.initials();
+        if is_stable(&initials) && initials.is_empty() {
+            return Err(search.trace(index));
+        }
+
+        for (mut initials, after) in process.transitions(&initials) {
+            // Anything that happens after a process terminates doesn't count.
+            initials.subtract(&E::tick());
+            if initials.can_perform_tau() {
+                initials.subtract(&E::tau());
+                search.add_edge(index, None, after.clone());
+            }
+            if !initials.is_empty() {
+                search.add_edge(index, Some(initials), after);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod deadlock_tests {
+    use super::*;
+
+    use proptest_attr_macro::proptest;
+
+    use crate::recursion::RecursionScope;
+    use crate::test_support::event;
+    use crate::test_support::NumberedEvent;
+    use crate::test_support::TestEvents;
+
+    #[test]
+    fn check_stop_deadlocks() {
+        assert_eq!(
+            is_deadlock_free(&CSP::<TestEvents, _, _>::stop()),
+            Err(vec![])
+        );
+    }
+
+    #[test]
+    fn check_skip_does_not_deadlock() {
+        assert_eq!(is_deadlock_free(&CSP::<TestEvents, _, _>::skip()), Ok(()));
+    }
+
+    #[proptest]
+    fn check_prefix_deadlocks_after_event(p: CSP<TestEvents, _, _>, event: NumberedEvent) {
+        // A prefix can only deadlock after performing its event.
+        let process = CSP::prefix(event.into(), p.clone());
+        match is_deadlock_free(&process) {
+            Ok(()) => assert_eq!(is_deadlock_free(&p), Ok(())),
+            Err(trace) => assert_eq!(trace[0], TestEvents::from(event)),
+        }
+    }
+
+    #[test]
+    fn check_shortest_deadlock() {
+        let process = CSP::external_choice(
+            CSP::prefix(event(0), CSP::prefix(event(1), CSP::stop())),
+            CSP::prefix(event(2), CSP::stop()),
+        );
+        assert_eq!(is_deadlock_free(&process), Err(vec![event(2)]));
+    }
+
+    #[test]
+    fn check_internal_choice_can_deadlock() {
+        // Stop ⊓ Skip is unstable, but can resolve to Stop.
+        let process = CSP::internal_choice(CSP::stop(), CSP::<TestEvents, _, _>::skip());
+        assert_eq!(is_deadlock_free(&process), Err(vec![]));
+    }
+
+    #[test]
+    fn check_recursive_process_does_not_deadlock() {
+        let scope = RecursionScope::new();
+        let p = scope.create_process("P");
+        scope.define(
+            p,
+            CSP::external_choice(
+                CSP::prefix(event(0), scope.recurse(p)),
+                CSP::prefix(event(1), CSP::skip()),
+            ),
+        );
+        assert_eq!(is_deadlock_free(&scope.recurse(p)), Ok(()));
+    }
+
+    #[test]
+    fn check_divergent_process_does_not_deadlock() {
+        // A process that diverges forever never reaches a stable state.
+        assert_eq!(is_deadlock_free(&CSP::<TestEvents, _, _>::div()), Ok(()));
+    }
+}
@@ -0,0 +1,185 @@
+// -*- coding: utf-8 -*-
+// ------------------------------------------------------------------------------------------------
+// Copyright © 2020, HST authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License.  You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied.  See the License for the specific language governing permissions and
+// limitations under the License.
+// ------------------------------------------------------------------------------------------------
+//! Checks whether a process is deterministic.
+//!
+//! In the failures-divergences model, a process is deterministic if it can never diverge, and if
+//! after any trace, there is no event that it can both perform and stably refuse.  We check this
+//! by normalizing the process (just like the specification of a refinement check), and looking at
+//! each of the stable states in each normalized state.
+
+use std::fmt::Display;
+use std::hash::Hash;
+
+use crate::csp::CSP;
+use crate::divergence::Divergence;
+use crate::event::EventSet;
+use crate::primitives::Tau;
+use crate::primitives::Tick;
+use crate::refinement::is_stable;
+use crate::refinement::refusal;
+use crate::refinement::Normalization;
+use crate::search::Search;
+
+/// Describes why a process is not deterministic.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Nondeterminism<E> {
+    /// After performing `trace`, the process can perform any of the events in `events`, but can
+    /// also reach a stable state where it refuses all of them.
+    AcceptAndRefuse { trace: Vec<E>, events: E },
+    /// The process can diverge.
+    Divergence(Divergence<E>),
+}
+
+/// Checks whether a process is deterministic in the failures-divergences model.  If not, returns
+/// the shortest trace after which the process behaves nondeterministically.
+pub fn is_deterministic<E, TauProof, TickProof>(
+    process: &CSP<E, TauProof, TickProof>,
+) -> Result<(), Nondeterminism<E>>
+where
+    E: Clone + Display + Eq + EventSet + Hash + Tau<TauProof> + Tick<TickProof>,
+    TauProof: Clone + Eq + Hash,
+    TickProof: Clone + Eq + Hash,
+{
+    let mut normalization = Normalization::new();
+    let initial_node = normalization.node(vec![process.clone()]);
+    let mut search = Search::new(initial_node);
+    while let Some(index) = search.next() {
+        let node = *search.state(index);
+        if let Some(cycle) = normalization.divergence(node) {
+            return Err(Nondeterminism::Divergence(Divergence::new(
+                search.trace(index),
+                &cycle,
+            )));
+        }
+
+        // Every visible event that any state can perform is a possible continuation of the
+        // trace, so no stable state is allowed to refuse any of them.
+        let initials = normalization
+            .processes(node)
+            .map(CSP::initials)
+            .collect::<Vec<_>>();
+        let mut accepted = E::empty();
+        for initials in &initials {
+            accepted.union(initials);
+        }
+        accepted.subtract(&E::tau());
+        for initials in initials.iter().filter(|initials| is_stable(*initials)) {
+            let mut events = refusal(initials);
+            events.intersect(&accepted);
+            if !events.is_empty() {
+                return Err(Nondeterminism::AcceptAndRefuse {
+                    trace: search.trace(index),
+                    events,
+                });
+            }
+        }
+
+        let (successors, _) = normalization.after(node, &accepted);
+        for (events, successor) in successors {
+            search.add_edge(index, Some(events), successor);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod determinism_tests {
+    use super::*;
+
+    use proptest_attr_macro::proptest;
+
+    use crate::recursion::RecursionScope;
+    use crate::test_support::event;
+    use crate::test_support::TestEvents;
+
+    #[proptest]
+    fn check_tau_free_processes_are_deterministic(p: CSP<TestEvents, _, _>) {
+        assert_eq!(is_deterministic(&p), Ok(()));
+    }
+
+    #[proptest]
+    fn check_internal_choice_with_itself_is_deterministic(p: CSP<TestEvents, _, _>) {
+        let process = CSP::internal_choice(p.clone(), p);
+        assert_eq!(is_deterministic(&process), Ok(()));
+    }
+
+    #[test]
+    fn check_internal_choice_is_nondeterministic() {
+        // a → Stop ⊓ (a → Stop □ b → Stop) can accept or refuse b.
+        let process = CSP::internal_choice(
+            CSP::prefix(event(0), CSP::stop()),
+            CSP::external_choice(
+                CSP::prefix(event(0), CSP::stop()),
+                CSP::prefix(event(1), CSP::stop()),
+            ),
+        );
+        assert_eq!(
+            is_deterministic(&process),
+            Err(Nondeterminism::AcceptAndRefuse {
+                trace: vec![],
+                events: event(1),
+            })
+        );
+    }
+
+    #[test]
+    fn check_ambiguous_prefixes_are_nondeterministic() {
+        // a → b → Stop □ a → (b → Stop □ c → Stop) can accept or refuse c after a.
+        let process = CSP::external_choice(
+            CSP::prefix(event(0), CSP::prefix(event(1), CSP::stop())),
+            CSP::prefix(
+                event(0),
+                CSP::external_choice(
+                    CSP::prefix(event(1), CSP::stop()),
+                    CSP::prefix(event(2), CSP::stop()),
+                ),
+            ),
+        );
+        assert_eq!(
+            is_deterministic(&process),
+            Err(Nondeterminism::AcceptAndRefuse {
+                trace: vec![event(0)],
+                events: event(2),
+            })
+        );
+    }
+
+    #[test]
+    fn check_divergent_process_is_nondeterministic() {
+        // c → (P \ {a}), where P = a → P
+        let scope = RecursionScope::new();
+        let p = scope.create_process("P");
+        scope.define(p, CSP::prefix(event(0), scope.recurse(p)));
+        let process = CSP::prefix(event(2), CSP::hide(scope.recurse(p), event(0)));
+        match is_deterministic(&process) {
+            Err(Nondeterminism::Divergence(divergence)) => {
+                assert_eq!(divergence.trace, vec![event(2)]);
+                assert_eq!(divergence.cycle.len(), 1);
+            }
+            result => panic!("Unexpected result {:?}", result),
+        }
+    }
+
+    #[test]
+    fn check_recursive_process_is_deterministic() {
+        let scope = RecursionScope::new();
+        let p = scope.create_process("P");
+        scope.define(
+            p,
+            CSP::prefix(event(0), CSP::prefix(event(1), scope.recurse(p))),
+        );
+        assert_eq!(is_deterministic(&scope.recurse(p)), Ok(()));
+    }
+}
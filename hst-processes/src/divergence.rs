@@ -0,0 +1,264 @@
+// -*- coding: utf-8 -*-
+// ------------------------------------------------------------------------------------------------
+// Copyright © 2020, HST authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License.  You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied.  See the License for the specific language governing permissions and
+// limitations under the License.
+// ------------------------------------------------------------------------------------------------
+
+//! Finds divergences — cycles of τ transitions, which a process can follow forever without ever
+//! performing a visible event.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::hash::Hash;
+
+use crate::csp::CSP;
+use crate::event::EventSet;
+use crate::primitives::Tau;
+use crate::primitives::Tick;
+use crate::search::Search;
+
+/// Describes how a process can diverge.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Divergence<E> {
+    /// The shortest visible trace after which the process can diverge.
+    pub trace: Vec<E>,
+    /// A loop of τ transitions that the process can follow forever after performing `trace`,
+    /// given as the `Display` of each state in the loop.
+    pub cycle: Vec<String>,
+}
+
+impl<E> Divergence<E> {
+    /// Describes a divergence after `trace`, following a τ cycle found by [`TauCycles::find`].
+    pub(crate) fn new<TauProof, TickProof>(
+        trace: Vec<E>,
+        cycle: &[CSP<E, TauProof, TickProof>],
+    ) -> Divergence<E>
+    where
+        E: Display,
+    {
+        Divergence {
+            trace,
+            cycle: cycle.iter().map(ToString::to_string).collect(),
+        }
+    }
+}
+
+/// Checks whether a process is divergence free — that is, whether it can never reach a cycle of
+/// τ transitions (a livelock), where it can keep performing internal events forever without ever
+/// performing a visible one.  If the process can diverge, returns the shortest trace that leads
+/// to a divergence, along with the τ cycle that it can get stuck in.
+pub fn is_divergence_free<E, TauProof, TickProof>(
+    process: &CSP<E, TauProof, TickProof>,
+) -> Result<(), Divergence<E>>
+where
+    E: Clone + Display + Eq + EventSet + Hash + Tau<TauProof> + Tick<TickProof>,
+    TauProof: Clone + Eq + Hash,
+    TickProof: Clone + Eq + Hash,
+{
+    let mut tau_cycles = TauCycles::new();
+    let mut search = Search::new(process.clone());
+    while let Some(index) = search.next() {
+        let process = search.state(index).clone();
+        if let Some(cycle) = tau_cycles.find(&process) {
+            return Err(Divergence::new(search.trace(index), &cycle));
+        }
+
+        // We already know that this process can't diverge via τ, so we only need to follow its
+        // visible events.  Anything that happens after a process terminates doesn't count.
+        let mut initials = process.initials();
+        initials.subtract(&E::tau());
+        initials.subtract(&E::tick());
+        for (initials, after) in process.transitions(&initials) {
+            search.add_edge(index, Some(initials), after);
+        }
+        for (_, after) in process.transitions(&E::tau()) {
+            search.add_edge(index, None, after);
+        }
+    }
+    Ok(())
+}
+
+/// Finds τ cycles that are reachable from a process via τ transitions.  We remember every process
+/// that we've proven cannot diverge, so that checking lots of related processes doesn't have to
+/// explore the same states over and over.
+pub(crate) struct TauCycles<E, TauProof, TickProof> {
+    non_divergent: HashSet<CSP<E, TauProof, TickProof>>,
+}
+
+// See the comment on `CSP` for why processes are safe to use as keys.
+#[allow(clippy::mutable_key_type)]
+impl<E, TauProof, TickProof> TauCycles<E, TauProof, TickProof>
+where
+    E: Clone + Eq + EventSet + Hash + Tau<TauProof> + Tick<TickProof>,
+    TauProof: Clone + Eq + Hash,
+    TickProof: Clone + Eq + Hash,
+{
+    pub(crate) fn new() -> TauCycles<E, TauProof, TickProof> {
+        TauCycles {
+            non_divergent: HashSet::new(),
+        }
+    }
+
+    /// Returns a cycle of τ transitions that `process` can reach without performing any visible
+    /// events, or `None` if `process` cannot diverge.  The first state of the cycle can reach
+    /// each of the others in turn via a τ, and the last can reach the first.
+    pub(crate) fn find(
+        &mut self,
+        process: &CSP<E, TauProof, TickProof>,
+    ) -> Option<Vec<CSP<E, TauProof, TickProof>>> {
+        if self.non_divergent.contains(process) {
+            return None;
+        }
+
+        // A depth-first search, which we perform by hand so that long chains of τs can't overflow
+        // the stack.  `path` holds the states on the current branch of the search, along with
+        // the τ successors of each that we haven't explored yet; `positions` tells us where each
+        // of them appears in `path`.
+        let mut path = vec![(process.clone(), Self::tau_successors(process))];
+        let mut positions = HashMap::new();
+        positions.insert(process.clone(), 0);
+        while let Some((_, successors)) = path.last_mut() {
+            match successors.pop() {
+                Some(next) => {
+                    if let Some(position) = positions.get(&next) {
+                        // We've found a τ transition back to a state on the current branch.
+                        return Some(
+                            path.drain(*position..)
+                                .map(|(process, _)| process)
+                                .collect(),
+                        );
+                    }
+                    if self.non_divergent.contains(&next) {
+                        continue;
+                    }
+                    positions.insert(next.clone(), path.len());
+                    let successors = Self::tau_successors(&next);
+                    path.push((next, successors));
+                }
+                None => {
+                    // We've explored everything reachable from this state without finding a
+                    // cycle.
+                    let (finished, _) = path.pop().unwrap();
+                    positions.remove(&finished);
+                    self.non_divergent.insert(finished);
+                }
+            }
+        }
+        None
+    }
+
+    fn tau_successors(process: &CSP<E, TauProof, TickProof>) -> Vec<CSP<E, TauProof, TickProof>> {
+        process
+            .transitions(&E::tau())
+            .map(|(_, after)| after)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod divergence_tests {
+    use super::*;
+
+    use proptest_attr_macro::proptest;
+
+    use crate::recursion::RecursionScope;
+    use crate::test_support::NumberedEvent;
+    use crate::test_support::TestEvents;
+
+    #[proptest]
+    fn check_tau_free_processes_do_not_diverge(p: CSP<TestEvents, _, _>) {
+        assert_eq!(TauCycles::new().find(&p), None);
+        assert_eq!(is_divergence_free(&p), Ok(()));
+    }
+
+    #[test]
+    fn check_div_diverges() {
+        let process = CSP::<TestEvents, _, _>::div();
+        assert_eq!(TauCycles::new().find(&process), Some(vec![process]));
+    }
+
+    #[test]
+    fn check_hidden_loop_diverges() {
+        // P = a → b → P, hiding {a, b}
+        let a = TestEvents::from(NumberedEvent(0));
+        let b = TestEvents::from(NumberedEvent(1));
+        let scope = RecursionScope::new();
+        let p = scope.create_process("P");
+        scope.define(
+            p,
+            CSP::prefix(a.clone(), CSP::prefix(b.clone(), scope.recurse(p))),
+        );
+        let mut ab = a.clone();
+        ab.union(&b);
+        let process = CSP::hide(CSP::prefix(a, scope.recurse(p)), ab);
+        let cycle = TauCycles::new().find(&process).unwrap();
+        assert_eq!(cycle.len(), 2);
+        assert!(!cycle.contains(&process));
+    }
+
+    #[test]
+    fn check_divergence_witness() {
+        // c → (a → (P \ {b}) □ b → Div), where P = b → P, diverges after either ⟨c, a⟩ or
+        // ⟨c, b⟩.  We should report one of those, and not a longer trace.
+        let a = TestEvents::from(NumberedEvent(0));
+        let b = TestEvents::from(NumberedEvent(1));
+        let c = TestEvents::from(NumberedEvent(2));
+        let scope = RecursionScope::new();
+        let p = scope.create_process("P");
+        scope.define(p, CSP::prefix(b.clone(), scope.recurse(p)));
+        let process = CSP::prefix(
+            c.clone(),
+            CSP::external_choice(
+                CSP::prefix(a.clone(), CSP::hide(scope.recurse(p), b.clone())),
+                CSP::prefix(b.clone(), CSP::div()),
+            ),
+        );
+        let divergence = is_divergence_free(&process).unwrap_err();
+        assert_eq!(divergence.trace.len(), 2);
+        assert_eq!(divergence.trace[0], c);
+        if divergence.trace[1] == b {
+            assert_eq!(divergence.cycle, vec!["Div".to_string()]);
+        } else {
+            assert_eq!(divergence.trace[1], a);
+            assert_eq!(divergence.cycle.len(), 1);
+        }
+    }
+
+    #[test]
+    fn check_sequential_composition_divergence() {
+        let process = CSP::sequential_composition(CSP::<TestEvents, _, _>::skip(), CSP::stop());
+        assert_eq!(is_divergence_free(&process), Ok(()));
+        let process = CSP::sequential_composition(CSP::<TestEvents, _, _>::skip(), CSP::div());
+        assert_eq!(
+            is_divergence_free(&process),
+            Err(Divergence {
+                trace: vec![],
+                cycle: vec!["Div".to_string()],
+            })
+        );
+    }
+
+    #[test]
+    fn check_hidden_finite_process_does_not_diverge() {
+        let a = TestEvents::from(NumberedEvent(0));
+        let process = CSP::hide(
+            CSP::prefix(a.clone(), CSP::prefix(a.clone(), CSP::skip())),
+            a,
+        );
+        let mut cycles = TauCycles::new();
+        assert_eq!(cycles.find(&process), None);
+        // The second time around we should be able to use what we learned the first time.
+        assert!(cycles.non_divergent.contains(&process));
+        assert_eq!(cycles.find(&process), None);
+    }
+}
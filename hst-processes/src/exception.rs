@@ -0,0 +1,203 @@
+// -*- coding: utf-8 -*-
+// ------------------------------------------------------------------------------------------------
+// Copyright © 2020, HST authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License.  You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied.  See the License for the specific language governing permissions and
+// limitations under the License.
+// ------------------------------------------------------------------------------------------------
+
+//! Defines the exception (`Θ`) operator.
+
+use std::fmt::Debug;
+use std::fmt::Display;
+
+use crate::csp::CSP;
+use crate::event::EventSet;
+use crate::primitives::Tau;
+use crate::primitives::Tick;
+use crate::probabilistic_choice::map_distribution;
+use crate::probabilistic_choice::Distribution;
+
+#[derive(Clone, Eq, Hash, PartialEq)]
+pub struct Exception<E, TauProof, TickProof> {
+    p: CSP<E, TauProof, TickProof>,
+    exceptions: E,
+    q: CSP<E, TauProof, TickProof>,
+}
+
+impl<E, TauProof, TickProof> Debug for Exception<E, TauProof, TickProof>
+where
+    E: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("Exception")
+            .field("p", &self.p)
+            .field("exceptions", &self.exceptions)
+            .field("q", &self.q)
+            .finish()
+    }
+}
+
+impl<E, TauProof, TickProof> Display for Exception<E, TauProof, TickProof>
+where
+    E: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} Θ{} {}", self.p, self.exceptions, self.q)
+    }
+}
+
+impl<E, TauProof, TickProof> Exception<E, TauProof, TickProof> {
+    pub(crate) fn new(
+        p: CSP<E, TauProof, TickProof>,
+        exceptions: E,
+        q: CSP<E, TauProof, TickProof>,
+    ) -> Exception<E, TauProof, TickProof> {
+        Exception { p, exceptions, q }
+    }
+}
+
+// Operational semantics for P Θ_A Q
+//
+//            P -a→ P'
+// 1)  ────────────────────────── a ∉ A ∪ {✔}
+//      P Θ_A Q -a→ P' Θ_A Q
+//
+//         P -a→ P'
+// 2)  ──────────────── a ∈ A
+//      P Θ_A Q -a→ Q
+//
+//         P -✔→ P'
+// 3)  ───────────────
+//      P Θ_A Q -✔→ P'
+//
+// Note that τ and ✔ can never be exceptions.
+
+impl<E, TauProof, TickProof> Exception<E, TauProof, TickProof>
+where
+    E: Clone + EventSet + Tau<TauProof> + Tick<TickProof>,
+    TauProof: Clone,
+    TickProof: Clone,
+{
+    /// Returns the events that will cause the exception to be thrown, which never includes τ or
+    /// ✔.
+    fn exceptions(&self) -> E {
+        let mut exceptions = self.exceptions.clone();
+        exceptions.subtract(&E::tau());
+        exceptions.subtract(&E::tick());
+        exceptions
+    }
+
+    pub(crate) fn distribution(&self) -> Option<Distribution<E, TauProof, TickProof>> {
+        let distribution = self.p.distribution()?;
+        Some(map_distribution(distribution, |p| {
+            CSP::exception(p, self.exceptions.clone(), self.q.clone())
+        }))
+    }
+
+    pub(crate) fn initials(&self) -> E {
+        self.p.initials()
+    }
+
+    pub(crate) fn transitions(
+        &self,
+        events: &E,
+    ) -> impl Iterator<Item = (E, CSP<E, TauProof, TickProof>)> + '_ {
+        let exceptions = self.exceptions();
+        self.p
+            .transitions(events)
+            .flat_map(move |(mut initials, after)| {
+                // If P performs an exception, the operator is resolved in favor of Q.
+                let mut thrown = initials.clone();
+                thrown.intersect(&exceptions);
+                initials.subtract(&exceptions);
+                let thrown_transition = if !thrown.is_empty() {
+                    Some((thrown, self.q.clone()))
+                } else {
+                    None
+                };
+
+                // If P terminates, there's nothing left to throw an exception.
+                let tick_transition = if initials.can_perform_tick() {
+                    initials.subtract(&E::tick());
+                    Some((E::tick(), after.clone()))
+                } else {
+                    None
+                };
+
+                // Otherwise, P can still throw an exception later on.
+                let other_transition = if !initials.is_empty() {
+                    Some((
+                        initials,
+                        CSP::exception(after, self.exceptions.clone(), self.q.clone()),
+                    ))
+                } else {
+                    None
+                };
+
+                thrown_transition
+                    .into_iter()
+                    .chain(tick_transition)
+                    .chain(other_transition)
+            })
+    }
+}
+
+#[cfg(test)]
+mod exception_tests {
+    use super::*;
+
+    use maplit::hashset;
+    use proptest_attr_macro::proptest;
+
+    use crate::csp::CSP;
+    use crate::maximal_traces::maximal_finite_traces;
+    use crate::test_support::NumberedEvent;
+    use crate::test_support::NumberedEvents;
+    use crate::test_support::TestEvents;
+
+    #[proptest]
+    fn check_exception_initials(
+        p: CSP<TestEvents, _, _>,
+        exceptions: NumberedEvents,
+        q: CSP<TestEvents, _, _>,
+    ) {
+        let process = CSP::exception(p.clone(), exceptions.into(), q);
+        assert_eq!(process.initials(), p.initials());
+    }
+
+    #[proptest]
+    fn check_no_exceptions_traces(p: CSP<TestEvents, _, _>, q: CSP<TestEvents, _, _>) {
+        let process = CSP::exception(p.clone(), TestEvents::empty(), q);
+        assert_eq!(maximal_finite_traces(&process), maximal_finite_traces(&p));
+    }
+
+    #[test]
+    fn check_exception_traces() {
+        let a = TestEvents::from(NumberedEvent(0));
+        let b = TestEvents::from(NumberedEvent(1));
+        let c = TestEvents::from(NumberedEvent(2));
+        let process = CSP::exception(
+            CSP::external_choice(
+                CSP::prefix(a.clone(), CSP::prefix(b.clone(), CSP::skip())),
+                CSP::prefix(b.clone(), CSP::skip()),
+            ),
+            a.clone(),
+            CSP::prefix(c.clone(), CSP::skip()),
+        );
+        assert_eq!(
+            maximal_finite_traces(&process),
+            hashset! {
+                vec![a, c, TestEvents::tick()],
+                vec![b, TestEvents::tick()],
+            }
+        );
+    }
+}
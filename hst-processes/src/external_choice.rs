@@ -19,12 +19,12 @@ use std::fmt::Debug;
 use std::fmt::Display;
 use std::marker::PhantomData;
 
-use itertools::Either;
-
 use crate::csp::CSP;
 use crate::event::EventSet;
 use crate::primitives::Tau;
 use crate::primitives::Tick;
+use crate::probabilistic_choice::map_distribution;
+use crate::probabilistic_choice::Distribution;
 
 #[derive(Clone, Eq, Hash, PartialEq)]
 pub struct ExternalChoice<E, TauProof, TickProof>(
@@ -87,6 +87,17 @@ where
     TauProof: Clone,
     TickProof: Clone,
 {
+    pub(crate) fn distribution(&self) -> Option<Distribution<E, TauProof, TickProof>> {
+        self.0.iter().enumerate().find_map(|(index, child)| {
+            let distribution = child.distribution()?;
+            Some(map_distribution(distribution, |after| {
+                let mut children = self.0.clone();
+                children[index] = after;
+                CSP::replicated_external_choice(children)
+            }))
+        })
+    }
+
     pub(crate) fn initials(&self) -> E {
         let mut initials = E::empty();
         for child in &self.0 {
@@ -101,40 +112,57 @@ where
     ) -> impl Iterator<Item = (E, CSP<E, TauProof, TickProof>)> + '_ {
         let events = events.clone();
         self.0.iter().enumerate().flat_map(move |(index, child)| {
-            child
-                .transitions(&events)
-                .flat_map(move |(mut initials, after)| {
-                    // If any child process can perform τ, then the external choice can as well,
-                    // but it _does not_ resolve the choice.
-                    let tau_transitions = if initials.can_perform_tau() {
-                        initials.subtract(&E::tau());
-                        // All other processes in the choice remain the same.  The process that
-                        // performed τ advances forward to its next state.
-                        let mut tau_children = self.0.clone();
-                        tau_children[index] = after.clone();
-                        Either::Left(std::iter::once((
-                            E::tau(),
-                            CSP::replicated_external_choice(tau_children),
-                        )))
-                    } else {
-                        Either::Right(std::iter::empty())
-                    };
-
-                    // If any child process can perform a non-τ event, then the external choice can
-                    // as well, and it _does_ resolve the choice.  Note that we've already removed
-                    // τ from `initials` at this point.
-                    let other_transitions = if !initials.is_empty() {
-                        Either::Left(std::iter::once((initials, after)))
-                    } else {
-                        Either::Right(std::iter::empty())
-                    };
-
-                    tau_transitions.chain(other_transitions)
+            child.transitions(&events).flat_map(move |transition| {
+                split_choice_transition(transition, |after| {
+                    // All other processes in the choice remain the same.  The process that
+                    // performed τ advances forward to its next state.
+                    let mut tau_children = self.0.clone();
+                    tau_children[index] = after;
+                    CSP::replicated_external_choice(tau_children)
                 })
+            })
         })
     }
 }
 
+/// The transitions that [`split_choice_transition`] produces for a single operand transition.
+pub(crate) type ChoiceTransitions<E, TauProof, TickProof> = std::iter::Chain<
+    std::option::IntoIter<(E, CSP<E, TauProof, TickProof>)>,
+    std::option::IntoIter<(E, CSP<E, TauProof, TickProof>)>,
+>;
+
+/// Handles one of the transitions of an operand of a choice operator.  If the operand can perform
+/// τ, then the choice can as well, but it _does not_ resolve the choice; `unresolved` constructs
+/// the process that the choice becomes in that case, given the operand's new state.  If the
+/// operand can perform a non-τ event, then the choice can as well, and it _does_ resolve the
+/// choice.
+pub(crate) fn split_choice_transition<E, TauProof, TickProof, F>(
+    (mut initials, after): (E, CSP<E, TauProof, TickProof>),
+    unresolved: F,
+) -> ChoiceTransitions<E, TauProof, TickProof>
+where
+    E: Clone + EventSet + Tau<TauProof>,
+    TauProof: Clone,
+    TickProof: Clone,
+    F: FnOnce(CSP<E, TauProof, TickProof>) -> CSP<E, TauProof, TickProof>,
+{
+    let tau_transition = if initials.can_perform_tau() {
+        initials.subtract(&E::tau());
+        Some((E::tau(), unresolved(after.clone())))
+    } else {
+        None
+    };
+
+    // Note that we've already removed τ from `initials` at this point.
+    let other_transition = if !initials.is_empty() {
+        Some((initials, after))
+    } else {
+        None
+    };
+
+    tau_transition.into_iter().chain(other_transition)
+}
+
 #[cfg(test)]
 mod external_choice_tests {
     use super::*;
@@ -146,6 +174,8 @@ mod external_choice_tests {
     use crate::maximal_traces::maximal_finite_traces;
     use crate::maximal_traces::MaximalTraces;
     use crate::test_support::NonemptyVec;
+    use crate::test_support::NumberedEvent;
+    use crate::test_support::NumberedEvents;
     use crate::test_support::TestEvents;
 
     #[test]
@@ -213,4 +243,28 @@ mod external_choice_tests {
                 .sum::<MaximalTraces<_>>()
         );
     }
+
+    #[proptest]
+    fn check_replicated_external_choice_over_events(indices: NumberedEvents) {
+        // □ x:A @ x → Stop
+        let indices = TestEvents::from(indices);
+        let process = CSP::replicated_external_choice_over(indices.clone(), |x| {
+            CSP::prefix(x.clone(), CSP::stop())
+        });
+        assert_eq!(process.initials(), indices);
+    }
+
+    #[test]
+    fn check_partitioned_replicated_external_choice_over_universe() {
+        // □ x:Σ @ a → Stop, which only has one equivalence class, no matter how many events there
+        // are.
+        let a = TestEvents::from(NumberedEvent(0));
+        let after = CSP::prefix(a.clone(), CSP::stop());
+        let process = CSP::partitioned_replicated_external_choice(
+            TestEvents::from(NumberedEvents::universe()),
+            |indices| vec![(indices.clone(), after.clone())],
+        );
+        assert_eq!(process.initials(), a.clone());
+        assert_eq!(maximal_finite_traces(&process), hashset! {vec![a]});
+    }
 }
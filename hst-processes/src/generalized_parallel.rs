@@ -0,0 +1,275 @@
+// -*- coding: utf-8 -*-
+// ------------------------------------------------------------------------------------------------
+// Copyright © 2020, HST authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License.  You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied.  See the License for the specific language governing permissions and
+// limitations under the License.
+// ------------------------------------------------------------------------------------------------
+
+//! Defines the generalized parallel (`[|A|]`) operator.
+
+use std::fmt::Debug;
+use std::fmt::Display;
+
+use crate::csp::CSP;
+use crate::event::EventSet;
+use crate::primitives::Tau;
+use crate::primitives::Tick;
+use crate::probabilistic_choice::map_distribution;
+use crate::probabilistic_choice::Distribution;
+
+#[derive(Clone, Eq, Hash, PartialEq)]
+pub struct GeneralizedParallel<E, TauProof, TickProof> {
+    p: CSP<E, TauProof, TickProof>,
+    alphabet: E,
+    q: CSP<E, TauProof, TickProof>,
+}
+
+impl<E, TauProof, TickProof> Debug for GeneralizedParallel<E, TauProof, TickProof>
+where
+    E: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("GeneralizedParallel")
+            .field("p", &self.p)
+            .field("alphabet", &self.alphabet)
+            .field("q", &self.q)
+            .finish()
+    }
+}
+
+impl<E, TauProof, TickProof> Display for GeneralizedParallel<E, TauProof, TickProof>
+where
+    E: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} [|{}|] {}", self.p, self.alphabet, self.q)
+    }
+}
+
+impl<E, TauProof, TickProof> GeneralizedParallel<E, TauProof, TickProof> {
+    pub(crate) fn new(
+        p: CSP<E, TauProof, TickProof>,
+        alphabet: E,
+        q: CSP<E, TauProof, TickProof>,
+    ) -> GeneralizedParallel<E, TauProof, TickProof> {
+        GeneralizedParallel { p, alphabet, q }
+    }
+}
+
+// Operational semantics for P [|A|] Q
+//
+//           P -a→ P'
+// 1)  ────────────────────── a ∉ A ∪ {✔}
+//      P [|A|] Q -a→ P' [|A|] Q
+//
+//           Q -a→ Q'
+// 2)  ────────────────────── a ∉ A ∪ {✔}
+//      P [|A|] Q -a→ P [|A|] Q'
+//
+//       P -a→ P'  Q -a→ Q'
+// 3)  ─────────────────────── a ∈ A ∪ {✔}
+//      P [|A|] Q -a→ P' [|A|] Q'
+//
+// Note that τ is never in A, so rules 1 and 2 let either side perform τ independently.  And ✔ is
+// always synchronized, so the composition only terminates when both sides can terminate.
+
+impl<E, TauProof, TickProof> GeneralizedParallel<E, TauProof, TickProof>
+where
+    E: Clone + EventSet + Tau<TauProof> + Tick<TickProof>,
+    TauProof: Clone,
+    TickProof: Clone,
+{
+    /// Returns the events that both sides must agree on: the alphabet, plus ✔ (for distributed
+    /// termination), but never τ.
+    fn synchronized(&self) -> E {
+        let mut synchronized = self.alphabet.clone();
+        synchronized.union(&E::tick());
+        synchronized.subtract(&E::tau());
+        synchronized
+    }
+
+    pub(crate) fn distribution(&self) -> Option<Distribution<E, TauProof, TickProof>> {
+        if let Some(distribution) = self.p.distribution() {
+            return Some(map_distribution(distribution, |p| {
+                CSP::generalized_parallel(p, self.alphabet.clone(), self.q.clone())
+            }));
+        }
+        let distribution = self.q.distribution()?;
+        Some(map_distribution(distribution, |q| {
+            CSP::generalized_parallel(self.p.clone(), self.alphabet.clone(), q)
+        }))
+    }
+
+    pub(crate) fn initials(&self) -> E {
+        let synchronized = self.synchronized();
+        let p_initials = self.p.initials();
+        let q_initials = self.q.initials();
+
+        // Either side can perform any unsynchronized event on its own.
+        let mut initials = p_initials.clone();
+        initials.union(&q_initials);
+        initials.subtract(&synchronized);
+
+        // Synchronized events need both sides to be able to perform them.
+        let mut both = p_initials;
+        both.intersect(&q_initials);
+        both.intersect(&synchronized);
+        initials.union(&both);
+        initials
+    }
+
+    pub(crate) fn transitions(
+        &self,
+        events: &E,
+    ) -> impl Iterator<Item = (E, CSP<E, TauProof, TickProof>)> + '_ {
+        let synchronized = self.synchronized();
+        let mut independent = events.clone();
+        independent.subtract(&synchronized);
+        let mut synchronized_events = events.clone();
+        synchronized_events.intersect(&synchronized);
+
+        // If P can perform an unsynchronized event (including τ) leading to P', then the
+        // composition can too, leading to P' [|A|] Q.
+        let p_transitions = self
+            .p
+            .transitions(&independent)
+            .map(move |(initials, p_after)| {
+                (
+                    initials,
+                    CSP::generalized_parallel(p_after, self.alphabet.clone(), self.q.clone()),
+                )
+            });
+
+        // Same for Q.
+        let q_transitions = self
+            .q
+            .transitions(&independent)
+            .map(move |(initials, q_after)| {
+                (
+                    initials,
+                    CSP::generalized_parallel(self.p.clone(), self.alphabet.clone(), q_after),
+                )
+            });
+
+        // For synchronized events, we find the events that P can perform, and then ask Q which of
+        // _those_ events it can perform.  Whatever's left is what the composition can perform,
+        // with both sides advancing.
+        let synchronized_transitions =
+            self.p
+                .transitions(&synchronized_events)
+                .flat_map(move |(p_initials, p_after)| {
+                    self.q
+                        .transitions(&p_initials)
+                        .map(move |(initials, q_after)| {
+                            (
+                                initials,
+                                CSP::generalized_parallel(
+                                    p_after.clone(),
+                                    self.alphabet.clone(),
+                                    q_after,
+                                ),
+                            )
+                        })
+                });
+
+        p_transitions
+            .chain(q_transitions)
+            .chain(synchronized_transitions)
+    }
+}
+
+#[cfg(test)]
+mod generalized_parallel_tests {
+    use super::*;
+
+    use maplit::hashset;
+    use proptest_attr_macro::proptest;
+
+    use crate::csp::CSP;
+    use crate::maximal_traces::maximal_finite_traces;
+    use crate::test_support::NumberedEvent;
+    use crate::test_support::NumberedEvents;
+    use crate::test_support::TestEvents;
+
+    #[proptest]
+    fn check_generalized_parallel_initials(
+        p: CSP<TestEvents, _, _>,
+        alphabet: NumberedEvents,
+        q: CSP<TestEvents, _, _>,
+    ) {
+        let alphabet = TestEvents::from(alphabet);
+        let process = CSP::generalized_parallel(p.clone(), alphabet.clone(), q.clone());
+        let mut synchronized = alphabet;
+        synchronized.union(&TestEvents::tick());
+        let mut expected = p.initials();
+        expected.union(&q.initials());
+        expected.subtract(&synchronized);
+        let mut both = p.initials();
+        both.intersect(&q.initials());
+        both.intersect(&synchronized);
+        expected.union(&both);
+        assert_eq!(process.initials(), expected);
+    }
+
+    #[proptest]
+    fn check_fully_synchronized_parallel_traces(p: CSP<TestEvents, _, _>) {
+        // Our arbitrary processes are all deterministic, so synchronizing a process with itself on
+        // every event shouldn't change its behavior.
+        let process =
+            CSP::generalized_parallel(p.clone(), NumberedEvents::universe().into(), p.clone());
+        assert_eq!(maximal_finite_traces(&process), maximal_finite_traces(&p));
+    }
+
+    #[proptest]
+    fn check_parallel_with_stop_traces(p: CSP<TestEvents, _, _>) {
+        // Stop refuses every synchronized event, including ✔, so the composition can't do
+        // anything.
+        let process = CSP::generalized_parallel(p, NumberedEvents::universe().into(), CSP::stop());
+        assert_eq!(maximal_finite_traces(&process), hashset! {vec![]});
+    }
+
+    #[test]
+    fn check_unsynchronized_parallel_traces() {
+        let a = TestEvents::from(NumberedEvent(0));
+        let b = TestEvents::from(NumberedEvent(1));
+        let process = CSP::generalized_parallel(
+            CSP::prefix(a.clone(), CSP::skip()),
+            TestEvents::empty(),
+            CSP::prefix(b.clone(), CSP::skip()),
+        );
+        assert_eq!(
+            maximal_finite_traces(&process),
+            hashset! {
+                vec![a.clone(), b.clone(), TestEvents::tick()],
+                vec![b, a, TestEvents::tick()],
+            }
+        );
+    }
+
+    #[test]
+    fn check_synchronized_parallel_traces() {
+        let a = TestEvents::from(NumberedEvent(0));
+        let b = TestEvents::from(NumberedEvent(1));
+        let c = TestEvents::from(NumberedEvent(2));
+        let process = CSP::generalized_parallel(
+            CSP::prefix(a.clone(), CSP::prefix(b.clone(), CSP::skip())),
+            a.clone(),
+            CSP::prefix(a.clone(), CSP::prefix(c.clone(), CSP::skip())),
+        );
+        assert_eq!(
+            maximal_finite_traces(&process),
+            hashset! {
+                vec![a.clone(), b.clone(), c.clone(), TestEvents::tick()],
+                vec![a, c, b, TestEvents::tick()],
+            }
+        );
+    }
+}
@@ -0,0 +1,230 @@
+// -*- coding: utf-8 -*-
+// ------------------------------------------------------------------------------------------------
+// Copyright © 2020, HST authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License.  You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied.  See the License for the specific language governing permissions and
+// limitations under the License.
+// ------------------------------------------------------------------------------------------------
+
+//! Defines the hiding (`\`) operator.
+
+use std::fmt::Debug;
+use std::fmt::Display;
+
+use crate::csp::CSP;
+use crate::event::EventSet;
+use crate::primitives::Tau;
+use crate::primitives::Tick;
+use crate::probabilistic_choice::map_distribution;
+use crate::probabilistic_choice::Distribution;
+
+#[derive(Clone, Eq, Hash, PartialEq)]
+pub struct Hiding<E, TauProof, TickProof> {
+    p: CSP<E, TauProof, TickProof>,
+    hidden: E,
+}
+
+impl<E, TauProof, TickProof> Debug for Hiding<E, TauProof, TickProof>
+where
+    E: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("Hiding")
+            .field("p", &self.p)
+            .field("hidden", &self.hidden)
+            .finish()
+    }
+}
+
+impl<E, TauProof, TickProof> Display for Hiding<E, TauProof, TickProof>
+where
+    E: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} \\ {}", self.p, self.hidden)
+    }
+}
+
+impl<E, TauProof, TickProof> Hiding<E, TauProof, TickProof> {
+    pub(crate) fn new(p: CSP<E, TauProof, TickProof>, hidden: E) -> Hiding<E, TauProof, TickProof> {
+        Hiding { p, hidden }
+    }
+}
+
+// Operational semantics for P \ A
+//
+//         P -a→ P'
+// 1)  ────────────────── a ∈ A
+//      P \ A -τ→ P' \ A
+//
+//         P -a→ P'
+// 2)  ────────────────── a ∉ A
+//      P \ A -a→ P' \ A
+//
+// Note that τ and ✔ can never be hidden.
+
+impl<E, TauProof, TickProof> Hiding<E, TauProof, TickProof>
+where
+    E: Clone + EventSet + Tau<TauProof> + Tick<TickProof>,
+    TauProof: Clone,
+    TickProof: Clone,
+{
+    /// Returns the events that will be hidden, which never includes τ or ✔.
+    fn hidden(&self) -> E {
+        let mut hidden = self.hidden.clone();
+        hidden.subtract(&E::tau());
+        hidden.subtract(&E::tick());
+        hidden
+    }
+
+    pub(crate) fn distribution(&self) -> Option<Distribution<E, TauProof, TickProof>> {
+        let distribution = self.p.distribution()?;
+        Some(map_distribution(distribution, |p| {
+            CSP::hide(p, self.hidden.clone())
+        }))
+    }
+
+    pub(crate) fn initials(&self) -> E {
+        let hidden = self.hidden();
+        let mut initials = self.p.initials();
+        let mut hidden_initials = initials.clone();
+        hidden_initials.intersect(&hidden);
+        if !hidden_initials.is_empty() {
+            initials.subtract(&hidden);
+            initials.union(&E::tau());
+        }
+        initials
+    }
+
+    pub(crate) fn transitions(
+        &self,
+        events: &E,
+    ) -> impl Iterator<Item = (E, CSP<E, TauProof, TickProof>)> + '_ {
+        let hidden = self.hidden();
+
+        // P can perform any of the requested events that aren't hidden.  And if the caller asked
+        // for τ, P can also perform any hidden event, since those will all turn into τ.
+        let mut p_events = events.clone();
+        p_events.subtract(&hidden);
+        if events.can_perform_tau() {
+            p_events.union(&hidden);
+        }
+
+        self.p
+            .transitions(&p_events)
+            .flat_map(move |(mut initials, after)| {
+                // Any hidden events that P performs become τ.  The rest remain visible.
+                let after = CSP::hide(after, self.hidden.clone());
+                let mut hidden_initials = initials.clone();
+                hidden_initials.intersect(&hidden);
+                initials.subtract(&hidden);
+                let tau_transition = if !hidden_initials.is_empty() {
+                    // If P could also perform a τ itself, that leads to the same place, so we
+                    // don't need a separate transition for it.
+                    initials.subtract(&E::tau());
+                    Some((E::tau(), after.clone()))
+                } else {
+                    None
+                };
+                let other_transition = if !initials.is_empty() {
+                    Some((initials, after))
+                } else {
+                    None
+                };
+                tau_transition.into_iter().chain(other_transition)
+            })
+    }
+}
+
+#[cfg(test)]
+mod hiding_tests {
+    use super::*;
+
+    use maplit::hashset;
+    use proptest_attr_macro::proptest;
+
+    use crate::csp::CSP;
+    use crate::maximal_traces::maximal_finite_traces;
+    use crate::test_support::NumberedEvent;
+    use crate::test_support::NumberedEvents;
+    use crate::test_support::TestEvents;
+
+    #[proptest]
+    fn check_hiding_initials(p: CSP<TestEvents, _, _>, hidden: NumberedEvents) {
+        let hidden = TestEvents::from(hidden);
+        let process = CSP::hide(p.clone(), hidden.clone());
+        let mut expected = p.initials();
+        let mut hidden_initials = expected.clone();
+        hidden_initials.intersect(&hidden);
+        if !hidden_initials.is_empty() {
+            expected.subtract(&hidden);
+            expected.union(&TestEvents::tau());
+        }
+        assert_eq!(process.initials(), expected);
+    }
+
+    #[proptest]
+    fn check_hiding_nothing_traces(p: CSP<TestEvents, _, _>) {
+        let process = CSP::hide(p.clone(), TestEvents::empty());
+        assert_eq!(maximal_finite_traces(&process), maximal_finite_traces(&p));
+    }
+
+    #[proptest]
+    fn check_hiding_everything_traces(p: CSP<TestEvents, _, _>) {
+        // Hiding every visible event should leave ✔ as the only thing that can appear in a trace.
+        let process = CSP::hide(p.clone(), TestEvents::universe());
+        assert_eq!(
+            maximal_finite_traces(&process),
+            maximal_finite_traces(&p).map(|trace| trace.retain(|e| *e == TestEvents::tick()))
+        );
+    }
+
+    #[test]
+    fn check_hiding_transitions() {
+        // Hiding a from a,b → Stop should give separate transitions for the hidden and visible
+        // events.
+        let a = TestEvents::from(NumberedEvent(0));
+        let b = TestEvents::from(NumberedEvent(1));
+        let mut ab = a.clone();
+        ab.union(&b);
+        let process = CSP::hide(CSP::prefix(ab, CSP::stop()), a.clone());
+        let after = CSP::hide(CSP::stop(), a);
+        let transitions = process
+            .transitions(&TestEvents::universe())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            transitions,
+            vec![
+                (TestEvents::tau(), after.clone()),
+                (b.clone(), after.clone())
+            ]
+        );
+
+        // And we should only get the transitions that were asked for.
+        let transitions = process.transitions(&b).collect::<Vec<_>>();
+        assert_eq!(transitions, vec![(b, after.clone())]);
+        let transitions = process.transitions(&TestEvents::tau()).collect::<Vec<_>>();
+        assert_eq!(transitions, vec![(TestEvents::tau(), after)]);
+    }
+
+    #[test]
+    fn check_hiding_traces() {
+        let a = TestEvents::from(NumberedEvent(0));
+        let b = TestEvents::from(NumberedEvent(1));
+        let process = CSP::hide(
+            CSP::prefix(a.clone(), CSP::prefix(b.clone(), CSP::skip())),
+            a,
+        );
+        assert_eq!(
+            maximal_finite_traces(&process),
+            hashset! {vec![b, TestEvents::tick()]}
+        );
+    }
+}
@@ -0,0 +1,236 @@
+// -*- coding: utf-8 -*-
+// ------------------------------------------------------------------------------------------------
+// Copyright © 2020, HST authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License.  You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied.  See the License for the specific language governing permissions and
+// limitations under the License.
+// ------------------------------------------------------------------------------------------------
+
+//! Defines the interleaving (`|||`) operator.
+
+use std::fmt::Debug;
+use std::fmt::Display;
+
+use itertools::Either;
+
+use crate::csp::CSP;
+use crate::event::EventSet;
+use crate::primitives::Tau;
+use crate::primitives::Tick;
+use crate::probabilistic_choice::map_distribution;
+use crate::probabilistic_choice::Distribution;
+
+#[derive(Clone, Eq, Hash, PartialEq)]
+pub struct Interleave<E, TauProof, TickProof>(Vec<CSP<E, TauProof, TickProof>>);
+
+impl<E, TauProof, TickProof> Debug for Interleave<E, TauProof, TickProof>
+where
+    E: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let mut tuple = f.debug_tuple("Interleave");
+        for child in &self.0 {
+            tuple.field(child);
+        }
+        tuple.finish()
+    }
+}
+
+impl<E, TauProof, TickProof> Display for Interleave<E, TauProof, TickProof>
+where
+    E: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if self.0.len() == 2 {
+            write!(f, "{} ||| {}", self.0[0], self.0[1])
+        } else {
+            f.write_str("||| ")?;
+            let mut set = f.debug_set();
+            for child in &self.0 {
+                set.entry(&format_args!("{}", child));
+            }
+            set.finish()
+        }
+    }
+}
+
+impl<E, TauProof, TickProof> Interleave<E, TauProof, TickProof> {
+    pub(crate) fn new(ps: Vec<CSP<E, TauProof, TickProof>>) -> Interleave<E, TauProof, TickProof> {
+        Interleave(ps)
+    }
+}
+
+// Operational semantics for ||| Ps
+//
+//                  P -a→ P'
+//  1)  ────────────────────────────────── P ∈ Ps, a ≠ ✔
+//       ||| Ps -a→ ||| (Ps ∖ {P} ∪ {P'})
+//
+//       ∀ P ∈ Ps • ∃ P' • P -✔→ P'
+//  2)  ────────────────────────────
+//            ||| Ps -✔→ Stop
+
+impl<E, TauProof, TickProof> Interleave<E, TauProof, TickProof>
+where
+    E: Clone + EventSet + Tau<TauProof> + Tick<TickProof>,
+    TauProof: Clone,
+    TickProof: Clone,
+{
+    pub(crate) fn distribution(&self) -> Option<Distribution<E, TauProof, TickProof>> {
+        self.0.iter().enumerate().find_map(|(index, child)| {
+            let distribution = child.distribution()?;
+            Some(map_distribution(distribution, |after| {
+                let mut children = self.0.clone();
+                children[index] = after;
+                CSP::replicated_interleave(children)
+            }))
+        })
+    }
+
+    pub(crate) fn initials(&self) -> E {
+        let mut initials = E::empty();
+        let mut can_terminate = true;
+        for child in &self.0 {
+            let mut child_initials = child.initials();
+            can_terminate &= child_initials.can_perform_tick();
+            child_initials.subtract(&E::tick());
+            initials.union(&child_initials);
+        }
+        if can_terminate {
+            initials.union(&E::tick());
+        }
+        initials
+    }
+
+    pub(crate) fn transitions(
+        &self,
+        events: &E,
+    ) -> impl Iterator<Item = (E, CSP<E, TauProof, TickProof>)> + '_ {
+        // Each child can perform any non-✔ event (including τ) on its own.  All other processes
+        // in the interleaving remain the same; the process that performed the event advances
+        // forward to its next state.
+        let mut independent = events.clone();
+        independent.subtract(&E::tick());
+        let other_transitions = self.0.iter().enumerate().flat_map(move |(index, child)| {
+            child
+                .transitions(&independent)
+                .map(move |(initials, after)| {
+                    let mut children = self.0.clone();
+                    children[index] = after;
+                    (initials, CSP::replicated_interleave(children))
+                })
+        });
+
+        // The interleaving can only terminate once every child can terminate.  Note that we don't
+        // care what each child's P' is; we just care that it exists.
+        let tick_transitions = if events.can_perform_tick()
+            && self
+                .0
+                .iter()
+                .all(|child| child.transitions(&E::tick()).next().is_some())
+        {
+            Either::Left(std::iter::once((E::tick(), CSP::stop())))
+        } else {
+            Either::Right(std::iter::empty())
+        };
+
+        other_transitions.chain(tick_transitions)
+    }
+}
+
+#[cfg(test)]
+mod interleave_tests {
+    use super::*;
+
+    use maplit::hashset;
+    use proptest_attr_macro::proptest;
+
+    use crate::csp::CSP;
+    use crate::maximal_traces::maximal_finite_traces;
+    use crate::test_support::NonemptyVec;
+    use crate::test_support::NumberedEvent;
+    use crate::test_support::TestEvents;
+
+    #[test]
+    fn check_empty_interleave_initials() {
+        let process = CSP::<TestEvents, _, _>::replicated_interleave(vec![]);
+        assert_eq!(process.initials(), TestEvents::tick());
+    }
+
+    #[test]
+    fn check_empty_interleave_traces() {
+        let process = CSP::<TestEvents, _, _>::replicated_interleave(vec![]);
+        assert_eq!(
+            maximal_finite_traces(&process),
+            hashset! {vec![TestEvents::tick()]}
+        );
+    }
+
+    #[proptest]
+    fn check_singleton_interleave_traces(p: CSP<TestEvents, _, _>) {
+        let process = CSP::replicated_interleave(vec![p.clone()]);
+        assert_eq!(maximal_finite_traces(&process), maximal_finite_traces(&p));
+    }
+
+    #[proptest]
+    fn check_replicated_interleave_initials(ps: NonemptyVec<CSP<TestEvents, _, _>>) {
+        let process = CSP::replicated_interleave(ps.vec.clone());
+        let mut expected = TestEvents::empty();
+        for p in &ps.vec {
+            expected.union(&p.initials());
+        }
+        expected.subtract(&TestEvents::tick());
+        if ps.vec.iter().all(|p| p.initials().can_perform_tick()) {
+            expected.union(&TestEvents::tick());
+        }
+        assert_eq!(process.initials(), expected);
+    }
+
+    #[proptest]
+    fn check_interleave_with_stop_traces(p: CSP<TestEvents, _, _>) {
+        // Stop never terminates, so the interleaving can do everything that P can, except for ✔.
+        let process = CSP::interleave(p.clone(), CSP::stop());
+        assert_eq!(
+            maximal_finite_traces(&process),
+            maximal_finite_traces(&p).map(|trace| {
+                if trace.ends_with(&[TestEvents::tick()]) {
+                    trace.pop();
+                }
+            })
+        );
+    }
+
+    #[proptest]
+    fn check_interleave_traces(p: CSP<TestEvents, _, _>, q: CSP<TestEvents, _, _>) {
+        // Interleaving is the same as generalized parallel with an empty alphabet.
+        let process = CSP::interleave(p.clone(), q.clone());
+        assert_eq!(
+            maximal_finite_traces(&process),
+            maximal_finite_traces(&CSP::generalized_parallel(p, TestEvents::empty(), q))
+        );
+    }
+
+    #[test]
+    fn check_doubleton_interleave_traces() {
+        let a = TestEvents::from(NumberedEvent(0));
+        let b = TestEvents::from(NumberedEvent(1));
+        let process = CSP::interleave(
+            CSP::prefix(a.clone(), CSP::skip()),
+            CSP::prefix(b.clone(), CSP::skip()),
+        );
+        assert_eq!(
+            maximal_finite_traces(&process),
+            hashset! {
+                vec![a.clone(), b.clone(), TestEvents::tick()],
+                vec![b, a, TestEvents::tick()],
+            }
+        );
+    }
+}
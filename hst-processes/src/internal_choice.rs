@@ -105,12 +105,16 @@ where
 mod internal_choice_tests {
     use super::*;
 
+    use maplit::hashset;
     use proptest_attr_macro::proptest;
 
     use crate::csp::CSP;
     use crate::maximal_traces::maximal_finite_traces;
     use crate::maximal_traces::MaximalTraces;
+    use crate::test_support::NonemptyNumberedEvents;
     use crate::test_support::NonemptyVec;
+    use crate::test_support::NumberedEvent;
+    use crate::test_support::NumberedEvents;
     use crate::test_support::TestEvents;
 
     #[proptest]
@@ -160,4 +164,54 @@ mod internal_choice_tests {
                 .sum::<MaximalTraces<_>>()
         );
     }
+
+    #[proptest]
+    fn check_replicated_internal_choice_over_events(indices: NonemptyNumberedEvents) {
+        // ⊓ x:A @ x → Stop
+        let indices = TestEvents::from(NumberedEvents::from(indices));
+        let process = CSP::replicated_internal_choice_over(indices.clone(), |x| {
+            CSP::prefix(x.clone(), CSP::stop())
+        });
+        assert_eq!(
+            maximal_finite_traces(&process),
+            indices
+                .into_iter()
+                .map(|x| vec![x])
+                .collect::<MaximalTraces<_>>()
+        );
+    }
+
+    #[test]
+    fn check_partitioned_replicated_internal_choice_over_universe() {
+        // ⊓ x:Σ @ (if x = a then a → Stop else b → Stop), which only has two equivalence classes,
+        // no matter how many events there are.
+        let a = TestEvents::from(NumberedEvent(0));
+        let b = TestEvents::from(NumberedEvent(1));
+        let process = CSP::partitioned_replicated_internal_choice(
+            TestEvents::from(NumberedEvents::universe()),
+            |indices| {
+                let mut others = indices.clone();
+                others.subtract(&a);
+                vec![
+                    (a.clone(), CSP::prefix(a.clone(), CSP::stop())),
+                    (others, CSP::prefix(b.clone(), CSP::stop())),
+                ]
+            },
+        );
+        assert_eq!(process.transitions(&TestEvents::tau()).count(), 2);
+        assert_eq!(maximal_finite_traces(&process), hashset! {vec![a], vec![b]});
+    }
+
+    #[test]
+    fn check_partitioned_replicated_internal_choice_ignores_empty_classes() {
+        let a = TestEvents::from(NumberedEvent(0));
+        let b = TestEvents::from(NumberedEvent(1));
+        let process = CSP::partitioned_replicated_internal_choice(a.clone(), |indices| {
+            vec![
+                (indices.clone(), CSP::prefix(a.clone(), CSP::stop())),
+                (b.clone(), CSP::prefix(b.clone(), CSP::stop())),
+            ]
+        });
+        assert_eq!(maximal_finite_traces(&process), hashset! {vec![a]});
+    }
 }
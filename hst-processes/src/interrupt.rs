@@ -0,0 +1,205 @@
+// -*- coding: utf-8 -*-
+// ------------------------------------------------------------------------------------------------
+// Copyright © 2020, HST authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License.  You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied.  See the License for the specific language governing permissions and
+// limitations under the License.
+// ------------------------------------------------------------------------------------------------
+
+//! Defines the interrupt (`△`) operator.
+
+use std::fmt::Debug;
+use std::fmt::Display;
+
+use crate::csp::CSP;
+use crate::event::EventSet;
+use crate::primitives::Tau;
+use crate::primitives::Tick;
+use crate::probabilistic_choice::map_distribution;
+use crate::probabilistic_choice::Distribution;
+
+#[derive(Clone, Eq, Hash, PartialEq)]
+pub struct Interrupt<E, TauProof, TickProof> {
+    p: CSP<E, TauProof, TickProof>,
+    q: CSP<E, TauProof, TickProof>,
+}
+
+impl<E, TauProof, TickProof> Debug for Interrupt<E, TauProof, TickProof>
+where
+    E: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("Interrupt")
+            .field("p", &self.p)
+            .field("q", &self.q)
+            .finish()
+    }
+}
+
+impl<E, TauProof, TickProof> Display for Interrupt<E, TauProof, TickProof>
+where
+    E: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} △ {}", self.p, self.q)
+    }
+}
+
+impl<E, TauProof, TickProof> Interrupt<E, TauProof, TickProof> {
+    pub(crate) fn new(
+        p: CSP<E, TauProof, TickProof>,
+        q: CSP<E, TauProof, TickProof>,
+    ) -> Interrupt<E, TauProof, TickProof> {
+        Interrupt { p, q }
+    }
+}
+
+// Operational semantics for P △ Q
+//
+//          P -a→ P'
+//  1)  ────────────────── a ≠ ✔
+//       P △ Q -a→ P' △ Q
+//
+//        P -✔→ P'
+//  2)  ────────────
+//       P △ Q -✔→ P'
+//
+//          Q -τ→ Q'
+//  3)  ──────────────────
+//       P △ Q -τ→ P △ Q'
+//
+//        Q -a→ Q'
+//  4)  ───────────── a ≠ τ
+//       P △ Q -a→ Q'
+
+impl<E, TauProof, TickProof> Interrupt<E, TauProof, TickProof>
+where
+    E: Clone + EventSet + Tau<TauProof> + Tick<TickProof>,
+    TauProof: Clone,
+    TickProof: Clone,
+{
+    pub(crate) fn distribution(&self) -> Option<Distribution<E, TauProof, TickProof>> {
+        if let Some(distribution) = self.p.distribution() {
+            return Some(map_distribution(distribution, |p| {
+                CSP::interrupt(p, self.q.clone())
+            }));
+        }
+        let distribution = self.q.distribution()?;
+        Some(map_distribution(distribution, |q| {
+            CSP::interrupt(self.p.clone(), q)
+        }))
+    }
+
+    pub(crate) fn initials(&self) -> E {
+        let mut initials = self.p.initials();
+        initials.union(&self.q.initials());
+        initials
+    }
+
+    pub(crate) fn transitions(
+        &self,
+        events: &E,
+    ) -> impl Iterator<Item = (E, CSP<E, TauProof, TickProof>)> + '_ {
+        // If P performs any event, the interrupt stays in place — unless that event is ✔, in
+        // which case P has finished and can no longer be interrupted.
+        let p_transitions = self
+            .p
+            .transitions(events)
+            .flat_map(move |(mut initials, after)| {
+                let tick_transition = if initials.can_perform_tick() {
+                    initials.subtract(&E::tick());
+                    Some((E::tick(), after.clone()))
+                } else {
+                    None
+                };
+                let other_transition = if !initials.is_empty() {
+                    Some((initials, CSP::interrupt(after, self.q.clone())))
+                } else {
+                    None
+                };
+                other_transition.into_iter().chain(tick_transition)
+            });
+
+        // If Q performs τ, it _does not_ resolve the interrupt.  If Q performs any other event,
+        // it _does_ resolve the interrupt, and P is discarded.
+        let q_transitions = self
+            .q
+            .transitions(events)
+            .flat_map(move |(mut initials, after)| {
+                let tau_transition = if initials.can_perform_tau() {
+                    initials.subtract(&E::tau());
+                    Some((E::tau(), CSP::interrupt(self.p.clone(), after.clone())))
+                } else {
+                    None
+                };
+                let other_transition = if !initials.is_empty() {
+                    Some((initials, after))
+                } else {
+                    None
+                };
+                tau_transition.into_iter().chain(other_transition)
+            });
+
+        p_transitions.chain(q_transitions)
+    }
+}
+
+#[cfg(test)]
+mod interrupt_tests {
+    use super::*;
+
+    use maplit::hashset;
+    use proptest_attr_macro::proptest;
+
+    use crate::csp::CSP;
+    use crate::maximal_traces::maximal_finite_traces;
+    use crate::test_support::NumberedEvent;
+    use crate::test_support::TestEvents;
+
+    #[proptest]
+    fn check_interrupt_initials(p: CSP<TestEvents, _, _>, q: CSP<TestEvents, _, _>) {
+        let process = CSP::interrupt(p.clone(), q.clone());
+        let mut expected = p.initials();
+        expected.union(&q.initials());
+        assert_eq!(process.initials(), expected);
+    }
+
+    #[proptest]
+    fn check_interrupt_by_stop_traces(p: CSP<TestEvents, _, _>) {
+        let process = CSP::interrupt(p.clone(), CSP::stop());
+        assert_eq!(maximal_finite_traces(&process), maximal_finite_traces(&p));
+    }
+
+    #[proptest]
+    fn check_interrupting_stop_traces(q: CSP<TestEvents, _, _>) {
+        let process = CSP::interrupt(CSP::stop(), q.clone());
+        assert_eq!(maximal_finite_traces(&process), maximal_finite_traces(&q));
+    }
+
+    #[test]
+    fn check_interrupt_traces() {
+        let a = TestEvents::from(NumberedEvent(0));
+        let b = TestEvents::from(NumberedEvent(1));
+        let c = TestEvents::from(NumberedEvent(2));
+        let process = CSP::interrupt(
+            CSP::prefix(a.clone(), CSP::prefix(b.clone(), CSP::skip())),
+            CSP::prefix(c.clone(), CSP::stop()),
+        );
+        assert_eq!(
+            maximal_finite_traces(&process),
+            hashset! {
+                vec![c.clone()],
+                vec![a.clone(), c.clone()],
+                vec![a.clone(), b.clone(), c],
+                vec![a, b, TestEvents::tick()],
+            }
+        );
+    }
+}
@@ -13,22 +13,66 @@
 // limitations under the License.
 // ------------------------------------------------------------------------------------------------
 
+mod alphabetized_parallel;
 mod csp;
+mod deadlock;
+mod determinism;
+mod divergence;
 mod event;
+mod exception;
 mod external_choice;
+mod generalized_parallel;
+mod hiding;
+mod interleave;
 mod internal_choice;
+mod interrupt;
+mod linked_parallel;
+mod markov;
 mod maximal_traces;
+mod operator;
 mod prefix;
+mod prefix_choice;
 mod primitives;
+mod prioritise;
+mod probabilistic_choice;
+mod recursion;
+mod refinement;
+mod renaming;
+mod search;
 mod sequential_composition;
+mod state_machine;
+mod timelock;
+mod timeout;
 
 pub use csp::CSP;
+pub use deadlock::is_deadlock_free;
+pub use determinism::is_deterministic;
+pub use determinism::Nondeterminism;
+pub use divergence::is_divergence_free;
+pub use divergence::Divergence;
 pub use event::DisjointSum;
 pub use event::EventSet;
+pub use markov::reachability_probability;
+pub use markov::Goal;
+pub use markov::ReachabilityProbability;
 pub use maximal_traces::maximal_finite_traces;
 pub use maximal_traces::MaximalTraces;
+pub use operator::Operator;
+pub use primitives::PrimitiveEvents;
 pub use primitives::Tau;
 pub use primitives::Tick;
+pub use primitives::Tock;
+pub use primitives::TockEvents;
+pub use probabilistic_choice::Distribution;
+pub use recursion::RecursionScope;
+pub use recursion::RecursiveProcessId;
+pub use refinement::refines_failures;
+pub use refinement::refines_failures_divergences;
+pub use refinement::refines_traces;
+pub use refinement::Counterexample;
+pub use renaming::Renaming;
+pub use state_machine::StepFunction;
+pub use timelock::is_timelock_free;
 
 #[cfg(test)]
 mod test_support;
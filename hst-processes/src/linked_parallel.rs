@@ -0,0 +1,379 @@
+// -*- coding: utf-8 -*-
+// ------------------------------------------------------------------------------------------------
+// Copyright © 2020, HST authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License.  You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied.  See the License for the specific language governing permissions and
+// limitations under the License.
+// ------------------------------------------------------------------------------------------------
+
+//! Defines the linked parallel (`[a ↔ b]`) operator.
+
+use std::fmt::Debug;
+use std::fmt::Display;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::rc::Rc;
+
+use itertools::Either;
+
+use crate::csp::CSP;
+use crate::event::EventSet;
+use crate::primitives::Tau;
+use crate::primitives::Tick;
+use crate::probabilistic_choice::map_distribution;
+use crate::probabilistic_choice::Distribution;
+use crate::renaming::Renaming;
+
+#[derive(Clone)]
+pub struct LinkedParallel<E, TauProof, TickProof> {
+    p: CSP<E, TauProof, TickProof>,
+    links: Rc<Links<E>>,
+    q: CSP<E, TauProof, TickProof>,
+}
+
+/// The channels that are linked together, along with the renaming that tells us which event on
+/// P's side of each link synchronizes with which event on Q's side.
+pub(crate) struct Links<E> {
+    pairs: Vec<(E, E)>,
+    renaming: Box<dyn Renaming<E>>,
+}
+
+impl<E> Links<E> {
+    pub(crate) fn new(pairs: Vec<(E, E)>, renaming: Box<dyn Renaming<E>>) -> Links<E> {
+        Links { pairs, renaming }
+    }
+}
+
+impl<E> Links<E>
+where
+    E: EventSet,
+{
+    /// Returns all of the events on P's side of any link.
+    fn left(&self) -> E {
+        let mut left = E::empty();
+        for (a, _) in &self.pairs {
+            left.union(a);
+        }
+        left
+    }
+
+    /// Returns all of the events on Q's side of any link.
+    fn right(&self) -> E {
+        let mut right = E::empty();
+        for (_, b) in &self.pairs {
+            right.union(b);
+        }
+        right
+    }
+}
+
+// Like renaming, we can't compare or hash the renaming relation, so two linked parallel processes
+// only have the same links if they share the same `Links` object.  Every state that a linked
+// parallel process can reach shares the original links.
+
+impl<E, TauProof, TickProof> PartialEq for LinkedParallel<E, TauProof, TickProof>
+where
+    E: PartialEq,
+    TauProof: PartialEq,
+    TickProof: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.p == other.p && Rc::ptr_eq(&self.links, &other.links) && self.q == other.q
+    }
+}
+
+impl<E, TauProof, TickProof> Eq for LinkedParallel<E, TauProof, TickProof>
+where
+    E: Eq,
+    TauProof: Eq,
+    TickProof: Eq,
+{
+}
+
+impl<E, TauProof, TickProof> Hash for LinkedParallel<E, TauProof, TickProof>
+where
+    E: Hash,
+    TauProof: Hash,
+    TickProof: Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.p.hash(state);
+        (Rc::as_ptr(&self.links) as usize).hash(state);
+        self.q.hash(state);
+    }
+}
+
+impl<E> Display for Links<E>
+where
+    E: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for (index, (a, b)) in self.pairs.iter().enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{} ↔ {}", a, b)?;
+        }
+        Ok(())
+    }
+}
+
+impl<E, TauProof, TickProof> Debug for LinkedParallel<E, TauProof, TickProof>
+where
+    E: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("LinkedParallel")
+            .field("p", &self.p)
+            .field("links", &self.links.pairs)
+            .field("renaming", &format_args!("{}", self.links.renaming))
+            .field("q", &self.q)
+            .finish()
+    }
+}
+
+impl<E, TauProof, TickProof> Display for LinkedParallel<E, TauProof, TickProof>
+where
+    E: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} [{}] {}", self.p, self.links, self.q)
+    }
+}
+
+impl<E, TauProof, TickProof> LinkedParallel<E, TauProof, TickProof> {
+    pub(crate) fn new(
+        p: CSP<E, TauProof, TickProof>,
+        links: Rc<Links<E>>,
+        q: CSP<E, TauProof, TickProof>,
+    ) -> LinkedParallel<E, TauProof, TickProof> {
+        LinkedParallel { p, links, q }
+    }
+}
+
+// Operational semantics for P [a ↔ b] Q
+//
+//              P -x→ P'
+// 1)  ──────────────────────────────── x ∉ a ∪ {✔}
+//      P [a ↔ b] Q -x→ P' [a ↔ b] Q
+//
+//              Q -x→ Q'
+// 2)  ──────────────────────────────── x ∉ b ∪ {✔}
+//      P [a ↔ b] Q -x→ P [a ↔ b] Q'
+//
+//        P -x→ P'  Q -y→ Q'
+// 3)  ───────────────────────────────── x ∈ a, y ∈ b, x R y
+//      P [a ↔ b] Q -τ→ P' [a ↔ b] Q'
+//
+//        P -✔→ P'  Q -✔→ Q'
+// 4)  ─────────────────────────────────
+//      P [a ↔ b] Q -✔→ P' [a ↔ b] Q'
+
+impl<E, TauProof, TickProof> LinkedParallel<E, TauProof, TickProof>
+where
+    E: Clone + EventSet + Tau<TauProof> + Tick<TickProof>,
+    TauProof: Clone,
+    TickProof: Clone,
+{
+    fn after(
+        &self,
+        p: CSP<E, TauProof, TickProof>,
+        q: CSP<E, TauProof, TickProof>,
+    ) -> CSP<E, TauProof, TickProof> {
+        CSP::shared_linked_parallel(p, self.links.clone(), q)
+    }
+
+    /// Returns the events that `events` is linked to, on Q's side of the `index`th link.
+    fn linked_events(&self, index: usize, events: &E) -> E {
+        let (a, b) = &self.links.pairs[index];
+        let mut source = events.clone();
+        source.intersect(a);
+        let mut linked = self.links.renaming.image(&source);
+        linked.intersect(b);
+        linked.subtract(&E::tau());
+        linked.subtract(&E::tick());
+        linked
+    }
+
+    /// Returns the τ transitions that occur when P and Q synchronize on a link.
+    fn link_transitions(
+        &self,
+    ) -> impl Iterator<Item = (CSP<E, TauProof, TickProof>, CSP<E, TauProof, TickProof>)> + '_ {
+        (0..self.links.pairs.len()).flat_map(move |index| {
+            let mut a = self.links.pairs[index].0.clone();
+            a.subtract(&E::tau());
+            a.subtract(&E::tick());
+            self.p
+                .transitions(&a)
+                .collect::<Vec<_>>()
+                .into_iter()
+                .flat_map(move |(p_initials, p_after)| {
+                    let linked = self.linked_events(index, &p_initials);
+                    self.q
+                        .transitions(&linked)
+                        .map(move |(_, q_after)| (p_after.clone(), q_after))
+                        .collect::<Vec<_>>()
+                })
+        })
+    }
+
+    pub(crate) fn distribution(&self) -> Option<Distribution<E, TauProof, TickProof>> {
+        if let Some(distribution) = self.p.distribution() {
+            return Some(map_distribution(distribution, |p| {
+                self.after(p, self.q.clone())
+            }));
+        }
+        let distribution = self.q.distribution()?;
+        Some(map_distribution(distribution, |q| {
+            self.after(self.p.clone(), q)
+        }))
+    }
+
+    pub(crate) fn initials(&self) -> E {
+        let p_initials = self.p.initials();
+        let q_initials = self.q.initials();
+
+        // Each side can perform any unlinked event on its own.
+        let mut initials = p_initials.clone();
+        initials.subtract(&self.links.left());
+        initials.subtract(&E::tick());
+        let mut q_only = q_initials.clone();
+        q_only.subtract(&self.links.right());
+        q_only.subtract(&E::tick());
+        initials.union(&q_only);
+
+        // Both sides must agree to terminate.
+        if p_initials.can_perform_tick() && q_initials.can_perform_tick() {
+            initials.union(&E::tick());
+        }
+
+        // Linked events turn into τ.
+        if self.link_transitions().next().is_some() {
+            initials.union(&E::tau());
+        }
+        initials
+    }
+
+    pub(crate) fn transitions(
+        &self,
+        events: &E,
+    ) -> impl Iterator<Item = (E, CSP<E, TauProof, TickProof>)> + '_ {
+        let mut p_events = events.clone();
+        p_events.subtract(&self.links.left());
+        p_events.subtract(&E::tick());
+        let mut q_events = events.clone();
+        q_events.subtract(&self.links.right());
+        q_events.subtract(&E::tick());
+
+        // If P can perform an unlinked event (including τ) leading to P', then the composition can
+        // too, leading to P' [a ↔ b] Q.
+        let p_transitions = self
+            .p
+            .transitions(&p_events)
+            .map(move |(initials, p_after)| (initials, self.after(p_after, self.q.clone())));
+
+        // Same for Q.
+        let q_transitions = self
+            .q
+            .transitions(&q_events)
+            .map(move |(initials, q_after)| (initials, self.after(self.p.clone(), q_after)));
+
+        // Linked events are hidden, so they show up as τ.
+        let link_transitions = if events.can_perform_tau() {
+            Either::Left(
+                self.link_transitions()
+                    .map(move |(p_after, q_after)| (E::tau(), self.after(p_after, q_after))),
+            )
+        } else {
+            Either::Right(std::iter::empty())
+        };
+
+        // Both sides must agree to terminate.
+        let tick_transitions = if events.can_perform_tick() {
+            Either::Left(
+                self.p
+                    .transitions(&E::tick())
+                    .flat_map(move |(_, p_after)| {
+                        self.q.transitions(&E::tick()).map(move |(_, q_after)| {
+                            (E::tick(), self.after(p_after.clone(), q_after))
+                        })
+                    }),
+            )
+        } else {
+            Either::Right(std::iter::empty())
+        };
+
+        p_transitions
+            .chain(q_transitions)
+            .chain(link_transitions)
+            .chain(tick_transitions)
+    }
+}
+
+#[cfg(test)]
+mod linked_parallel_tests {
+    use super::*;
+
+    use maplit::hashset;
+    use proptest_attr_macro::proptest;
+
+    use crate::csp::CSP;
+    use crate::maximal_traces::maximal_finite_traces;
+    use crate::test_support::NumberedEvent;
+    use crate::test_support::NumberedRenaming;
+    use crate::test_support::TestEvents;
+
+    #[proptest]
+    fn check_unlinked_parallel_traces(p: CSP<TestEvents, _, _>, q: CSP<TestEvents, _, _>) {
+        let process = CSP::linked_parallel(p.clone(), vec![], NumberedRenaming(vec![]), q.clone());
+        assert_eq!(
+            maximal_finite_traces(&process),
+            maximal_finite_traces(&CSP::interleave(p, q))
+        );
+    }
+
+    #[test]
+    fn check_linked_parallel_pipeline() {
+        let input = NumberedEvent(0);
+        let middle_out = NumberedEvent(1);
+        let middle_in = NumberedEvent(2);
+        let output = NumberedEvent(3);
+        let left = CSP::prefix(input.into(), CSP::prefix(middle_out.into(), CSP::skip()));
+        let right = CSP::prefix(middle_in.into(), CSP::prefix(output.into(), CSP::skip()));
+        let process = CSP::linked_parallel(
+            left,
+            vec![(middle_out.into(), middle_in.into())],
+            NumberedRenaming(vec![(middle_out, middle_in)]),
+            right,
+        );
+        assert_eq!(process.initials(), input.into());
+        assert_eq!(
+            maximal_finite_traces(&process),
+            hashset! {vec![input.into(), output.into(), TestEvents::tick()]}
+        );
+    }
+
+    #[test]
+    fn check_linked_parallel_does_not_link_other_events() {
+        // P performing an event on Q's side of the link doesn't synchronize with anything.
+        let a = NumberedEvent(0);
+        let b = NumberedEvent(1);
+        let process = CSP::linked_parallel(
+            CSP::prefix(b.into(), CSP::skip()),
+            vec![(a.into(), b.into())],
+            NumberedRenaming(vec![(a, b)]),
+            CSP::skip(),
+        );
+        assert_eq!(
+            maximal_finite_traces(&process),
+            hashset! {vec![b.into(), TestEvents::tick()]}
+        );
+    }
+}
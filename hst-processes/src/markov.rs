@@ -0,0 +1,378 @@
+// -*- coding: utf-8 -*-
+// ------------------------------------------------------------------------------------------------
+// Copyright © 2020, HST authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License.  You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied.  See the License for the specific language governing permissions and
+// limitations under the License.
+// ------------------------------------------------------------------------------------------------
+
+//! Analyzes processes that contain probabilistic choices as Markov decision processes.
+//!
+//! Each state of the process is either a _probabilistic_ state (one with a
+//! [`distribution`][CSP::distribution], which chooses its successor according to that
+//! distribution), or a _nondeterministic_ state (any other process).  In a nondeterministic state,
+//! an adversary gets to choose which transition to follow.  The adversary resolves all
+//! nondeterminism: internal choices, and also which visible events the environment offers.
+//!
+//! A probabilistic choice is resolved as soon as it's reached, even when it's nested inside of
+//! some other operator, like `(P [p]+ Q) ||| R`.  The adversary only gets to make a choice once
+//! every probabilistic choice that's currently running has been resolved.
+
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::hash::Hash;
+
+use crate::csp::CSP;
+use crate::event::EventSet;
+use crate::primitives::Tau;
+use crate::primitives::Tick;
+
+/// The condition whose probability we want to calculate.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Goal<E> {
+    /// The process performs any event in this set.
+    Events(E),
+    /// The process deadlocks: it reaches a state where it can't perform any events, without
+    /// having terminated successfully.
+    Deadlock,
+}
+
+/// The minimum and maximum probability (over all possible adversaries) of reaching a goal.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ReachabilityProbability {
+    pub min: f64,
+    pub max: f64,
+}
+
+/// The convergence threshold for value iteration.
+const EPSILON: f64 = 1e-12;
+
+/// The maximum number of rounds of value iteration that we'll perform.
+const MAX_ITERATIONS: usize = 1_000_000;
+
+/// What happens when the process follows one transition.
+#[derive(Clone, Copy)]
+enum Outcome {
+    /// The transition reaches the goal.
+    Goal,
+    /// The transition can never reach the goal (because the process has terminated).
+    Never,
+    /// The transition leads to another state.
+    State(usize),
+}
+
+enum State {
+    Probabilistic(Vec<(f64, usize)>),
+    Nondeterministic(Vec<Outcome>),
+}
+
+/// Calculates the minimum and maximum probability that `process` reaches `goal`.
+/// Panics if the probabilities don't converge.
+pub fn reachability_probability<E, TauProof, TickProof>(
+    process: &CSP<E, TauProof, TickProof>,
+    goal: &Goal<E>,
+) -> ReachabilityProbability
+where
+    E: Clone + Eq + EventSet + Hash + Tau<TauProof> + Tick<TickProof>,
+    TauProof: Clone + Eq + Hash,
+    TickProof: Clone + Eq + Hash,
+{
+    let states = explore(process, goal);
+    ReachabilityProbability {
+        min: value_iteration(&states, f64::min),
+        max: value_iteration(&states, f64::max),
+    }
+}
+
+/// Finds all of the states that are reachable from `process`, and the transitions between them.
+/// The initial state is always state 0.
+// See the comment on `CSP` for why processes are safe to use as keys.
+#[allow(clippy::mutable_key_type)]
+fn explore<E, TauProof, TickProof>(
+    process: &CSP<E, TauProof, TickProof>,
+    goal: &Goal<E>,
+) -> Vec<State>
+where
+    E: Clone + Eq + EventSet + Hash + Tau<TauProof> + Tick<TickProof>,
+    TauProof: Clone + Eq + Hash,
+    TickProof: Clone + Eq + Hash,
+{
+    let mut ids = HashMap::new();
+    let mut queue = VecDeque::new();
+    let mut states = Vec::new();
+    ids.insert(process.clone(), 0);
+    queue.push_back(process.clone());
+
+    let mut id_of = |process: CSP<E, TauProof, TickProof>,
+                     queue: &mut VecDeque<CSP<E, TauProof, TickProof>>| {
+        let next_id = ids.len();
+        *ids.entry(process.clone()).or_insert_with(|| {
+            queue.push_back(process);
+            next_id
+        })
+    };
+
+    while let Some(process) = queue.pop_front() {
+        if let Some(distribution) = process.distribution() {
+            let successors = distribution
+                .into_iter()
+                .map(|(probability, after)| (probability, id_of(after, &mut queue)))
+                .collect();
+            states.push(State::Probabilistic(successors));
+            continue;
+        }
+
+        let mut outcomes = Vec::new();
+        for (mut initials, after) in process.transitions(&process.initials()) {
+            if let Goal::Events(events) = goal {
+                let mut reached = initials.clone();
+                reached.intersect(events);
+                if !reached.is_empty() {
+                    outcomes.push(Outcome::Goal);
+                    initials.subtract(events);
+                }
+            }
+            if initials.can_perform_tick() {
+                // Successful termination is never a deadlock, and we don't care what happens
+                // afterwards.
+                outcomes.push(Outcome::Never);
+                initials.subtract(&E::tick());
+            }
+            if !initials.is_empty() {
+                outcomes.push(Outcome::State(id_of(after, &mut queue)));
+            }
+        }
+        if outcomes.is_empty() && *goal == Goal::Deadlock {
+            outcomes.push(Outcome::Goal);
+        }
+        states.push(State::Nondeterministic(outcomes));
+    }
+    states
+}
+
+/// Calculates the probability of reaching the goal from the initial state, where `choose` is
+/// how the adversary picks between two alternatives.  Panics if the values haven't converged after
+/// `MAX_ITERATIONS` rounds, since we'd otherwise return a probability that could be arbitrarily
+/// wrong.
+fn value_iteration<F>(states: &[State], choose: F) -> f64
+where
+    F: Fn(f64, f64) -> f64,
+{
+    let value_of = |values: &[f64], outcome: &Outcome| match outcome {
+        Outcome::Goal => 1.0,
+        Outcome::Never => 0.0,
+        Outcome::State(id) => values[*id],
+    };
+
+    let mut values = vec![0.0; states.len()];
+    for _ in 0..MAX_ITERATIONS {
+        let mut delta: f64 = 0.0;
+        for (id, state) in states.iter().enumerate() {
+            let value = match state {
+                State::Probabilistic(successors) => successors
+                    .iter()
+                    .map(|(probability, successor)| probability * values[*successor])
+                    .sum(),
+                State::Nondeterministic(outcomes) => outcomes
+                    .iter()
+                    .map(|outcome| value_of(&values, outcome))
+                    .fold(None, |result: Option<f64>, value| {
+                        Some(result.map_or(value, |result| choose(result, value)))
+                    })
+                    .unwrap_or(0.0),
+            };
+            delta = delta.max((value - values[id]).abs());
+            values[id] = value;
+        }
+        if delta < EPSILON {
+            return values[0];
+        }
+    }
+    panic!(
+        "Value iteration did not converge after {} iterations",
+        MAX_ITERATIONS
+    );
+}
+
+#[cfg(test)]
+mod markov_tests {
+    use super::*;
+
+    use crate::recursion::RecursionScope;
+    use crate::test_support::NumberedEvent;
+    use crate::test_support::TestEvents;
+
+    fn assert_probability(actual: ReachabilityProbability, min: f64, max: f64) {
+        assert!(
+            (actual.min - min).abs() < 1e-9 && (actual.max - max).abs() < 1e-9,
+            "expected [{}, {}], got [{}, {}]",
+            min,
+            max,
+            actual.min,
+            actual.max
+        );
+    }
+
+    #[test]
+    fn check_probabilistic_choice_probability() {
+        let a = TestEvents::from(NumberedEvent(0));
+        let b = TestEvents::from(NumberedEvent(1));
+        let process = CSP::probabilistic_choice(
+            CSP::prefix(a.clone(), CSP::skip()),
+            0.3,
+            CSP::prefix(b, CSP::skip()),
+        );
+        assert_probability(
+            reachability_probability(&process, &Goal::Events(a)),
+            0.3,
+            0.3,
+        );
+        assert_probability(
+            reachability_probability(&process, &Goal::Deadlock),
+            0.0,
+            0.0,
+        );
+    }
+
+    #[test]
+    fn check_internal_choice_probability() {
+        let a = TestEvents::from(NumberedEvent(0));
+        let b = TestEvents::from(NumberedEvent(1));
+        let process = CSP::internal_choice(
+            CSP::prefix(a.clone(), CSP::skip()),
+            CSP::prefix(b, CSP::stop()),
+        );
+        assert_probability(
+            reachability_probability(&process, &Goal::Events(a)),
+            0.0,
+            1.0,
+        );
+        assert_probability(
+            reachability_probability(&process, &Goal::Deadlock),
+            0.0,
+            1.0,
+        );
+    }
+
+    #[test]
+    fn check_nested_probability() {
+        // (a → Skip [0.5]+ b → Stop) ⊓ Stop
+        let a = TestEvents::from(NumberedEvent(0));
+        let b = TestEvents::from(NumberedEvent(1));
+        let coin = CSP::probabilistic_choice(
+            CSP::prefix(a.clone(), CSP::skip()),
+            0.5,
+            CSP::prefix(b, CSP::stop()),
+        );
+        let process = CSP::internal_choice(coin, CSP::stop());
+        assert_probability(
+            reachability_probability(&process, &Goal::Events(a)),
+            0.0,
+            0.5,
+        );
+        assert_probability(
+            reachability_probability(&process, &Goal::Deadlock),
+            0.5,
+            1.0,
+        );
+    }
+
+    #[test]
+    fn check_interleaved_probability() {
+        // (a → Stop [0.25]+ b → Stop) ||| c → Stop
+        let a = TestEvents::from(NumberedEvent(0));
+        let b = TestEvents::from(NumberedEvent(1));
+        let c = TestEvents::from(NumberedEvent(2));
+        let coin = CSP::probabilistic_choice(
+            CSP::prefix(a.clone(), CSP::stop()),
+            0.25,
+            CSP::prefix(b.clone(), CSP::stop()),
+        );
+        let process = CSP::interleave(coin, CSP::prefix(c, CSP::stop()));
+        assert_probability(
+            reachability_probability(&process, &Goal::Events(a)),
+            0.25,
+            0.25,
+        );
+        assert_probability(
+            reachability_probability(&process, &Goal::Events(b)),
+            0.75,
+            0.75,
+        );
+    }
+
+    #[test]
+    fn check_external_choice_probability() {
+        // (a → Stop [0.25]+ b → Stop) □ c → Stop.  The adversary can always choose c instead, but
+        // can only choose a if the probabilistic choice has already picked it.
+        let a = TestEvents::from(NumberedEvent(0));
+        let b = TestEvents::from(NumberedEvent(1));
+        let c = TestEvents::from(NumberedEvent(2));
+        let coin = CSP::probabilistic_choice(
+            CSP::prefix(a.clone(), CSP::stop()),
+            0.25,
+            CSP::prefix(b, CSP::stop()),
+        );
+        let process = CSP::external_choice(coin, CSP::prefix(c.clone(), CSP::stop()));
+        assert_probability(
+            reachability_probability(&process, &Goal::Events(a)),
+            0.0,
+            0.25,
+        );
+        assert_probability(
+            reachability_probability(&process, &Goal::Events(c)),
+            0.0,
+            1.0,
+        );
+    }
+
+    #[test]
+    fn check_recursive_probability() {
+        // P = a → Stop [0.5]+ b → P, which eventually performs a with probability 1.
+        let a = TestEvents::from(NumberedEvent(0));
+        let b = TestEvents::from(NumberedEvent(1));
+        let scope = RecursionScope::new();
+        let p = scope.create_process("P");
+        scope.define(
+            p,
+            CSP::probabilistic_choice(
+                CSP::prefix(a.clone(), CSP::stop()),
+                0.5,
+                CSP::prefix(b.clone(), scope.recurse(p)),
+            ),
+        );
+        let process = scope.recurse(p);
+        assert_probability(
+            reachability_probability(&process, &Goal::Events(a)),
+            1.0,
+            1.0,
+        );
+        // The probability of performing b at least once is 1/2.
+        assert_probability(
+            reachability_probability(&process, &Goal::Events(b)),
+            0.5,
+            0.5,
+        );
+    }
+
+    #[test]
+    fn check_deadlock_probability() {
+        assert_probability(
+            reachability_probability(&CSP::<TestEvents, _, _>::stop(), &Goal::Deadlock),
+            1.0,
+            1.0,
+        );
+        assert_probability(
+            reachability_probability(&CSP::<TestEvents, _, _>::skip(), &Goal::Deadlock),
+            0.0,
+            0.0,
+        );
+    }
+}
@@ -42,7 +42,7 @@ where
         MaximalTraces(traces)
     }
 
-    pub fn iter<'a>(&'a self) -> impl Iterator<Item = &'a Vec<E>> {
+    pub fn iter(&self) -> impl Iterator<Item = &Vec<E>> {
         self.0.iter()
     }
 }
@@ -80,6 +80,15 @@ where
     }
 }
 
+impl<E> Default for MaximalTraces<E>
+where
+    E: Eq + Hash,
+{
+    fn default() -> MaximalTraces<E> {
+        MaximalTraces::new()
+    }
+}
+
 impl<E> Debug for MaximalTraces<E>
 where
     E: Debug + Eq + Hash,
@@ -159,6 +168,7 @@ where
 }
 
 #[cfg(test)]
+#[allow(clippy::items_after_test_module)]
 mod maximal_traces_tests {
     use super::*;
 
@@ -202,7 +212,7 @@ where
         TickProof: Clone + PartialEq,
     {
         // If `process` already appears earlier in the current trace, then we've found a cycle.
-        if previous_processes.contains(&process) {
+        if previous_processes.contains(process) {
             result.insert(current_trace.clone());
             return;
         }
@@ -0,0 +1,345 @@
+// -*- coding: utf-8 -*-
+// ------------------------------------------------------------------------------------------------
+// Copyright © 2020, HST authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License.  You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied.  See the License for the specific language governing permissions and
+// limitations under the License.
+// ------------------------------------------------------------------------------------------------
+
+//! Lets you define your own CSP operators outside of this crate.
+
+use std::any::Any;
+use std::fmt::Debug;
+use std::fmt::Display;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::rc::Rc;
+
+use crate::csp::CSP;
+use crate::probabilistic_choice::Distribution;
+
+/// A CSP operator that's defined outside of this crate.  You can wrap an instance of your operator
+/// in a `CSP` process using [`CSP::operator`][crate::CSP::operator], and it will then behave just
+/// like any of the built-in operators.
+///
+/// Just like the built-in operators, `transitions` should only return events that are in the
+/// `events` set that is passed in, and should group together any events that lead to the same
+/// process.  Two operators that are equal must have the same behavior.
+pub trait Operator<E, TauProof, TickProof>: Debug + Display + Eq + Hash + 'static {
+    /// Returns the set of events that this process can perform.
+    fn initials(&self) -> E;
+
+    /// Returns the transitions that this process can perform, restricted to `events`.
+    fn transitions<'a>(
+        &'a self,
+        events: &E,
+    ) -> Box<dyn Iterator<Item = (E, CSP<E, TauProof, TickProof>)> + 'a>;
+
+    /// If this process makes a probabilistic choice, returns the probability distribution over the
+    /// processes that it can become; see [`CSP::distribution`][crate::CSP::distribution].  We
+    /// can't see inside of a custom operator, so if it contains a probabilistic choice that's
+    /// currently running, it has to pass that choice's distribution up itself.  The default
+    /// returns `None`, so by default a custom operator looks nondeterministic to a Markov
+    /// analysis, even if its τ transitions resolve a probabilistic choice.
+    fn distribution(&self) -> Option<Distribution<E, TauProof, TickProof>> {
+        None
+    }
+}
+
+/// An object-safe version of [`Operator`], which lets us hide the type of the operator.
+trait DynOperator<E, TauProof, TickProof> {
+    fn as_any(&self) -> &dyn Any;
+    fn dyn_eq(&self, other: &dyn Any) -> bool;
+    fn dyn_hash(&self, state: &mut dyn Hasher);
+    fn fmt_debug(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result;
+    fn fmt_display(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result;
+    fn initials(&self) -> E;
+    fn transitions<'a>(
+        &'a self,
+        events: &E,
+    ) -> Box<dyn Iterator<Item = (E, CSP<E, TauProof, TickProof>)> + 'a>;
+    fn distribution(&self) -> Option<Distribution<E, TauProof, TickProof>>;
+}
+
+impl<E, TauProof, TickProof, O> DynOperator<E, TauProof, TickProof> for O
+where
+    O: Operator<E, TauProof, TickProof>,
+{
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn dyn_eq(&self, other: &dyn Any) -> bool {
+        // Operators of different types are never equal.
+        match other.downcast_ref::<O>() {
+            Some(other) => self == other,
+            None => false,
+        }
+    }
+
+    fn dyn_hash(&self, mut state: &mut dyn Hasher) {
+        self.hash(&mut state);
+    }
+
+    fn fmt_debug(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        (self as &dyn Debug).fmt(f)
+    }
+
+    fn fmt_display(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        (self as &dyn Display).fmt(f)
+    }
+
+    fn initials(&self) -> E {
+        Operator::initials(self)
+    }
+
+    fn transitions<'a>(
+        &'a self,
+        events: &E,
+    ) -> Box<dyn Iterator<Item = (E, CSP<E, TauProof, TickProof>)> + 'a> {
+        Operator::transitions(self, events)
+    }
+
+    fn distribution(&self) -> Option<Distribution<E, TauProof, TickProof>> {
+        Operator::distribution(self)
+    }
+}
+
+/// A process that is defined by a custom [`Operator`].  We hide the type of the operator, so that
+/// all custom operators can share a single variant of the process type.
+pub struct Custom<E, TauProof, TickProof>(Rc<dyn DynOperator<E, TauProof, TickProof>>);
+
+impl<E, TauProof, TickProof> Custom<E, TauProof, TickProof> {
+    pub(crate) fn new<O>(operator: O) -> Custom<E, TauProof, TickProof>
+    where
+        O: Operator<E, TauProof, TickProof>,
+    {
+        Custom(Rc::new(operator))
+    }
+}
+
+impl<E, TauProof, TickProof> Clone for Custom<E, TauProof, TickProof> {
+    fn clone(&self) -> Self {
+        Custom(self.0.clone())
+    }
+}
+
+impl<E, TauProof, TickProof> PartialEq for Custom<E, TauProof, TickProof> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.dyn_eq(other.0.as_any())
+    }
+}
+
+impl<E, TauProof, TickProof> Eq for Custom<E, TauProof, TickProof> {}
+
+impl<E, TauProof, TickProof> Hash for Custom<E, TauProof, TickProof> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.dyn_hash(state);
+    }
+}
+
+impl<E, TauProof, TickProof> Debug for Custom<E, TauProof, TickProof> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        self.0.fmt_debug(f)
+    }
+}
+
+impl<E, TauProof, TickProof> Display for Custom<E, TauProof, TickProof> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        self.0.fmt_display(f)
+    }
+}
+
+impl<E, TauProof, TickProof> Custom<E, TauProof, TickProof> {
+    pub(crate) fn initials(&self) -> E {
+        self.0.initials()
+    }
+
+    pub(crate) fn transitions(
+        &self,
+        events: &E,
+    ) -> impl Iterator<Item = (E, CSP<E, TauProof, TickProof>)> + '_ {
+        self.0.transitions(events)
+    }
+
+    pub(crate) fn distribution(&self) -> Option<Distribution<E, TauProof, TickProof>> {
+        self.0.distribution()
+    }
+}
+
+#[cfg(test)]
+mod operator_tests {
+    use std::fmt::Display;
+
+    use maplit::hashset;
+    use proptest_attr_macro::proptest;
+
+    use crate::csp::CSP;
+    use crate::event::EventSet;
+    use crate::event::Here;
+    use crate::maximal_traces::maximal_finite_traces;
+    use crate::operator::Operator;
+    use crate::primitives::Tau;
+    use crate::probabilistic_choice::Distribution;
+    use crate::test_support::NumberedEvent;
+    use crate::test_support::TestEvents;
+
+    /// An operator that performs its `n` events in order, and then stops.
+    #[derive(Debug, Eq, Hash, PartialEq)]
+    struct Countdown(u16);
+
+    impl Display for Countdown {
+        fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            write!(f, "Countdown({})", self.0)
+        }
+    }
+
+    impl Operator<TestEvents, Here, Here> for Countdown {
+        fn initials(&self) -> TestEvents {
+            if self.0 == 0 {
+                TestEvents::empty()
+            } else {
+                NumberedEvent(self.0).into()
+            }
+        }
+
+        fn transitions<'a>(
+            &'a self,
+            events: &TestEvents,
+        ) -> Box<dyn Iterator<Item = (TestEvents, CSP<TestEvents, Here, Here>)> + 'a> {
+            let mut initials = self.initials();
+            initials.intersect(events);
+            if initials.is_empty() {
+                return Box::new(std::iter::empty());
+            }
+            Box::new(std::iter::once((
+                initials,
+                CSP::operator(Countdown(self.0 - 1)),
+            )))
+        }
+    }
+
+    /// An operator that performs τ forever.
+    #[derive(Debug, Eq, Hash, PartialEq)]
+    struct Spin;
+
+    impl Display for Spin {
+        fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            f.write_str("Spin")
+        }
+    }
+
+    impl Operator<TestEvents, Here, Here> for Spin {
+        fn initials(&self) -> TestEvents {
+            TestEvents::tau()
+        }
+
+        fn transitions<'a>(
+            &'a self,
+            events: &TestEvents,
+        ) -> Box<dyn Iterator<Item = (TestEvents, CSP<TestEvents, Here, Here>)> + 'a> {
+            if !events.can_perform_tau() {
+                return Box::new(std::iter::empty());
+            }
+            Box::new(std::iter::once((TestEvents::tau(), CSP::operator(Spin))))
+        }
+    }
+
+    /// An operator that flips a fair coin, and then behaves like either `Countdown(1)` or
+    /// `Countdown(2)`.
+    #[derive(Debug, Eq, Hash, PartialEq)]
+    struct Coin;
+
+    impl Display for Coin {
+        fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            f.write_str("Coin")
+        }
+    }
+
+    impl Operator<TestEvents, Here, Here> for Coin {
+        fn initials(&self) -> TestEvents {
+            TestEvents::tau()
+        }
+
+        fn transitions<'a>(
+            &'a self,
+            events: &TestEvents,
+        ) -> Box<dyn Iterator<Item = (TestEvents, CSP<TestEvents, Here, Here>)> + 'a> {
+            if !events.can_perform_tau() {
+                return Box::new(std::iter::empty());
+            }
+            Box::new(
+                vec![
+                    (TestEvents::tau(), CSP::operator(Countdown(1))),
+                    (TestEvents::tau(), CSP::operator(Countdown(2))),
+                ]
+                .into_iter(),
+            )
+        }
+
+        fn distribution(&self) -> Option<Distribution<TestEvents, Here, Here>> {
+            Some(vec![
+                (0.5, CSP::operator(Countdown(1))),
+                (0.5, CSP::operator(Countdown(2))),
+            ])
+        }
+    }
+
+    #[test]
+    fn check_custom_operator_display() {
+        let process = CSP::operator(Countdown(2));
+        assert_eq!(process.to_string(), "Countdown(2)");
+        assert_eq!(format!("{:?}", process), "Countdown(2)");
+    }
+
+    #[proptest]
+    fn check_custom_operator_equality(a: u16, b: u16) {
+        assert_eq!(
+            CSP::operator(Countdown(a)) == CSP::operator(Countdown(b)),
+            a == b
+        );
+        assert_ne!(CSP::operator(Countdown(a)), CSP::operator(Spin));
+    }
+
+    #[test]
+    fn check_custom_operator_distribution() {
+        assert_eq!(CSP::operator(Countdown(2)).distribution(), None);
+        assert_eq!(
+            CSP::operator(Coin).distribution(),
+            Some(vec![
+                (0.5, CSP::operator(Countdown(1))),
+                (0.5, CSP::operator(Countdown(2))),
+            ])
+        );
+        // The distribution is passed up through any built-in operators that contain it.
+        assert_eq!(
+            CSP::interleave(CSP::operator(Coin), CSP::stop()).distribution(),
+            Some(vec![
+                (
+                    0.5,
+                    CSP::interleave(CSP::operator(Countdown(1)), CSP::stop())
+                ),
+                (
+                    0.5,
+                    CSP::interleave(CSP::operator(Countdown(2)), CSP::stop())
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn check_custom_operator_traces() {
+        let process = CSP::external_choice(CSP::operator(Countdown(2)), CSP::operator(Spin));
+        assert_eq!(
+            maximal_finite_traces(&process),
+            hashset! {vec![NumberedEvent(2).into(), NumberedEvent(1).into()]}
+        );
+    }
+}
@@ -0,0 +1,241 @@
+// -*- coding: utf-8 -*-
+// ------------------------------------------------------------------------------------------------
+// Copyright © 2020, HST authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License.  You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied.  See the License for the specific language governing permissions and
+// limitations under the License.
+// ------------------------------------------------------------------------------------------------
+
+//! Defines the prefix choice (`?x:A → P(x)`) operator.
+
+use std::fmt::Debug;
+use std::fmt::Display;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::rc::Rc;
+
+use crate::csp::CSP;
+use crate::event::EventSet;
+
+/// Splits a set of events into equivalence classes, and returns the process that each class leads
+/// to.  Each class must be a subset of the events that are passed in, and the classes must be
+/// disjoint.
+pub(crate) type Continuation<E, TauProof, TickProof> =
+    dyn Fn(&E) -> Vec<(E, CSP<E, TauProof, TickProof>)>;
+
+pub struct PrefixChoice<E, TauProof, TickProof> {
+    initials: E,
+    continuation: Rc<Continuation<E, TauProof, TickProof>>,
+}
+
+impl<E, TauProof, TickProof> Clone for PrefixChoice<E, TauProof, TickProof>
+where
+    E: Clone,
+{
+    fn clone(&self) -> Self {
+        PrefixChoice {
+            initials: self.initials.clone(),
+            continuation: self.continuation.clone(),
+        }
+    }
+}
+
+// We can't compare or hash closures, so two prefix choices only have the same continuation if
+// they share the same closure.
+
+impl<E, TauProof, TickProof> PartialEq for PrefixChoice<E, TauProof, TickProof>
+where
+    E: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.initials == other.initials && Rc::ptr_eq(&self.continuation, &other.continuation)
+    }
+}
+
+impl<E, TauProof, TickProof> Eq for PrefixChoice<E, TauProof, TickProof> where E: Eq {}
+
+impl<E, TauProof, TickProof> Hash for PrefixChoice<E, TauProof, TickProof>
+where
+    E: Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.initials.hash(state);
+        (Rc::as_ptr(&self.continuation) as *const () as usize).hash(state);
+    }
+}
+
+impl<E, TauProof, TickProof> Debug for PrefixChoice<E, TauProof, TickProof>
+where
+    E: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "PrefixChoice({:?})", self.initials)
+    }
+}
+
+impl<E, TauProof, TickProof> Display for PrefixChoice<E, TauProof, TickProof>
+where
+    E: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "?x:{} → P(x)", self.initials)
+    }
+}
+
+impl<E, TauProof, TickProof> PrefixChoice<E, TauProof, TickProof> {
+    pub(crate) fn new(
+        initials: E,
+        continuation: Rc<Continuation<E, TauProof, TickProof>>,
+    ) -> PrefixChoice<E, TauProof, TickProof> {
+        PrefixChoice {
+            initials,
+            continuation,
+        }
+    }
+}
+
+// Operational semantics for ?x:A → P(x)
+//
+// 1) ─────────────────────── a ∈ A
+//     ?x:A → P(x) -a→ P(a)
+
+impl<E, TauProof, TickProof> PrefixChoice<E, TauProof, TickProof>
+where
+    E: Clone + EventSet,
+    TauProof: Clone,
+    TickProof: Clone,
+{
+    pub(crate) fn initials(&self) -> E {
+        self.initials.clone()
+    }
+
+    pub(crate) fn transitions(
+        &self,
+        events: &E,
+    ) -> impl Iterator<Item = (E, CSP<E, TauProof, TickProof>)> {
+        let mut events = events.clone();
+        events.intersect(&self.initials);
+        let classes = if events.is_empty() {
+            Vec::new()
+        } else {
+            (self.continuation)(&events)
+        };
+
+        // Make sure that a badly behaved continuation can't produce events that we didn't ask for.
+        classes.into_iter().filter_map(move |(mut class, after)| {
+            class.intersect(&events);
+            if class.is_empty() {
+                None
+            } else {
+                Some((class, after))
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod prefix_choice_tests {
+    use maplit::hashset;
+    use proptest_attr_macro::proptest;
+
+    use crate::csp::CSP;
+    use crate::event::EventSet;
+    use crate::maximal_traces::maximal_finite_traces;
+    use crate::test_support::NonemptyNumberedEvents;
+    use crate::test_support::NumberedEvent;
+    use crate::test_support::NumberedEvents;
+    use crate::test_support::TestEvents;
+
+    /// Returns the number of the (single) event in `events`.
+    fn event_number(events: &TestEvents) -> u16 {
+        (0..)
+            .find(|n| events.1.contains(NumberedEvent(*n)))
+            .unwrap()
+    }
+
+    #[proptest]
+    fn check_prefix_choice_initials(
+        initials: NonemptyNumberedEvents,
+        after: CSP<TestEvents, _, _>,
+    ) {
+        let initials = TestEvents::from_b(initials.into());
+        let process = CSP::prefix_choice(initials.clone(), move |_| after.clone());
+        assert_eq!(process.initials(), initials);
+    }
+
+    #[proptest]
+    fn check_constant_prefix_choice_traces(
+        initials: NonemptyNumberedEvents,
+        after: CSP<TestEvents, _, _>,
+    ) {
+        let initials = TestEvents::from_b(initials.into());
+        let continuation = after.clone();
+        let process = CSP::partitioned_prefix_choice(initials.clone(), move |events| {
+            vec![(events.clone(), continuation.clone())]
+        });
+        assert_eq!(
+            maximal_finite_traces(&process),
+            maximal_finite_traces(&CSP::prefix(initials, after))
+        );
+    }
+
+    #[test]
+    fn check_prefix_choice_traces() {
+        // in?x → out!x → Stop
+        let inputs = TestEvents::from(NumberedEvents::from(hashset! {
+            NumberedEvent(0),
+            NumberedEvent(1),
+            NumberedEvent(2),
+        }));
+        let process = CSP::prefix_choice(inputs, |x| {
+            let output = NumberedEvent(event_number(x) + 10);
+            CSP::prefix(output.into(), CSP::stop())
+        });
+        assert_eq!(
+            maximal_finite_traces(&process),
+            hashset! {
+                vec![NumberedEvent(0).into(), NumberedEvent(10).into()],
+                vec![NumberedEvent(1).into(), NumberedEvent(11).into()],
+                vec![NumberedEvent(2).into(), NumberedEvent(12).into()],
+            }
+        );
+    }
+
+    #[test]
+    fn check_partitioned_prefix_choice_transitions() {
+        let a = TestEvents::from(NumberedEvent(0));
+        let b = TestEvents::from(NumberedEvent(1));
+        let c = TestEvents::from(NumberedEvent(2));
+        let mut initials = a.clone();
+        initials.union(&b);
+        initials.union(&c);
+        let odd = b.clone();
+        let process = CSP::partitioned_prefix_choice(initials, move |events| {
+            let mut evens = events.clone();
+            evens.subtract(&odd);
+            let mut odds = events.clone();
+            odds.intersect(&odd);
+            vec![(evens, CSP::stop()), (odds, CSP::skip())]
+        });
+        let mut evens = a;
+        evens.union(&c);
+        let transitions = process
+            .transitions(&TestEvents::universe())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            transitions,
+            vec![(evens, CSP::stop()), (b.clone(), CSP::skip())]
+        );
+
+        // Classes that don't overlap the requested events are dropped.
+        let transitions = process.transitions(&b).collect::<Vec<_>>();
+        assert_eq!(transitions, vec![(b, CSP::skip())]);
+    }
+}
@@ -348,6 +348,209 @@ mod primitive_events_tests {
     }
 }
 
+//-------------------------------------------------------------------------------------------------
+// Discrete time
+
+/// The _tock_ event.  In tock-CSP, this visible event marks the passage of one unit of time.
+/// Unlike τ and ✔, tock isn't built into every event type; you opt into discrete time by including
+/// [`TockEvents`] in your event type.
+pub trait Tock<Proof> {
+    fn tock() -> Self;
+    fn can_perform_tock(&self) -> bool;
+}
+
+#[derive(Clone, Eq, Hash, PartialEq)]
+pub struct TockEvents {
+    contains_tock: bool,
+}
+
+impl Debug for TockEvents {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if self.contains_tock {
+            write!(f, "TockEvents {{tock}}")
+        } else {
+            write!(f, "TockEvents {{}}")
+        }
+    }
+}
+
+impl Display for TockEvents {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if self.contains_tock {
+            write!(f, "{{tock}}")
+        } else {
+            write!(f, "{{}}")
+        }
+    }
+}
+
+impl EventSet for TockEvents {
+    fn empty() -> Self {
+        TockEvents {
+            contains_tock: false,
+        }
+    }
+
+    fn intersect(&mut self, other: &Self) {
+        self.contains_tock &= other.contains_tock;
+    }
+
+    fn is_empty(&self) -> bool {
+        !self.contains_tock
+    }
+
+    fn negate(&mut self) {
+        self.contains_tock = !self.contains_tock;
+    }
+
+    fn subtract(&mut self, other: &Self) {
+        self.contains_tock &= !other.contains_tock;
+    }
+
+    fn union(&mut self, other: &Self) {
+        self.contains_tock |= other.contains_tock;
+    }
+
+    fn universe() -> Self {
+        TockEvents {
+            contains_tock: true,
+        }
+    }
+}
+
+impl IntoIterator for TockEvents {
+    type Item = TockEvents;
+    type IntoIter = std::option::IntoIter<TockEvents>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        if self.contains_tock {
+            Some(self).into_iter()
+        } else {
+            None.into_iter()
+        }
+    }
+}
+
+impl Tock<()> for TockEvents {
+    fn tock() -> Self {
+        TockEvents {
+            contains_tock: true,
+        }
+    }
+
+    fn can_perform_tock(&self) -> bool {
+        self.contains_tock
+    }
+}
+
+impl<E, Tail> Tock<Here> for DisjointSum<E, Tail>
+where
+    E: Tock<()>,
+    Tail: EventSet,
+{
+    fn tock() -> Self {
+        DisjointSum::from_a(E::tock())
+    }
+
+    fn can_perform_tock(&self) -> bool {
+        self.0.can_perform_tock()
+    }
+}
+
+impl<Head, Tail, TailIndex> Tock<There<TailIndex>> for DisjointSum<Head, Tail>
+where
+    Head: EventSet,
+    Tail: Tock<TailIndex>,
+{
+    fn tock() -> Self {
+        DisjointSum::from_b(Tail::tock())
+    }
+
+    fn can_perform_tock(&self) -> bool {
+        self.1.can_perform_tock()
+    }
+}
+
+#[cfg(test)]
+mod tock_events_tests {
+    use super::*;
+
+    use proptest_attr_macro::proptest;
+
+    use crate::test_support::NumberedEvents;
+    use crate::test_support::TimedTestEvents;
+
+    #[test]
+    fn can_check_for_tock() {
+        assert!(!TockEvents::empty().can_perform_tock());
+        assert!(TockEvents::tock().can_perform_tock());
+        assert!(TockEvents::universe().can_perform_tock());
+    }
+
+    #[test]
+    fn can_check_sum_for_tock() {
+        assert!(!TimedTestEvents::empty().can_perform_tock());
+        assert!(TimedTestEvents::tock().can_perform_tock());
+        assert!(!TimedTestEvents::tau().can_perform_tock());
+        assert!(!TimedTestEvents::tick().can_perform_tock());
+        assert!(!TimedTestEvents::tock().can_perform_tau());
+        assert!(!TimedTestEvents::tock().can_perform_tick());
+    }
+
+    #[proptest]
+    fn rest_of_sum_does_not_affect_tock(rest: NumberedEvents) {
+        let mut whole = TimedTestEvents::tock();
+        whole.union(&TimedTestEvents::from(rest));
+        assert!(whole.can_perform_tock());
+    }
+
+    #[test]
+    fn can_enumerate() {
+        let collect = |events: TockEvents| events.into_iter().collect::<Vec<_>>();
+        assert_eq!(collect(TockEvents::empty()), vec![]);
+        assert_eq!(collect(TockEvents::tock()), vec![TockEvents::tock()]);
+    }
+}
+
+#[cfg(test)]
+mod discrete_time_tests {
+    use super::*;
+
+    use maplit::hashset;
+
+    use crate::maximal_traces::maximal_finite_traces;
+    use crate::test_support::tocks;
+    use crate::test_support::NumberedEvent;
+    use crate::test_support::TimedTestEvents;
+
+    #[test]
+    fn check_wait_traces() {
+        let process = CSP::<TimedTestEvents, _, _>::wait(3);
+        let mut expected = tocks(3);
+        expected.push(TimedTestEvents::tick());
+        assert_eq!(maximal_finite_traces(&process), hashset! {expected});
+    }
+
+    #[test]
+    fn check_urgent_events_prevent_time_passing() {
+        // (a → Stop) □ (tock → b → Stop), where a is urgent.
+        let a = TimedTestEvents::from(NumberedEvent(0));
+        let b = TimedTestEvents::from(NumberedEvent(1));
+        let p = CSP::external_choice(
+            CSP::prefix(a.clone(), CSP::stop()),
+            CSP::prefix(TimedTestEvents::tock(), CSP::prefix(b.clone(), CSP::stop())),
+        );
+        let lazy = p.clone();
+        let process = CSP::urgent(p, a.clone());
+        assert_eq!(process.initials(), a.clone());
+        assert_eq!(maximal_finite_traces(&process), hashset! {vec![a.clone()]});
+        assert_eq!(
+            maximal_finite_traces(&lazy),
+            hashset! {vec![a], vec![TimedTestEvents::tock(), b]}
+        );
+    }
+}
+
 //-------------------------------------------------------------------------------------------------
 // Stop
 
@@ -479,3 +682,321 @@ mod skip_tests {
         );
     }
 }
+
+//-------------------------------------------------------------------------------------------------
+// Run
+
+#[derive(Clone, Eq, Hash, PartialEq)]
+pub(crate) struct Run<E>(E);
+
+impl<E> Run<E> {
+    pub(crate) fn new(alphabet: E) -> Run<E> {
+        Run(alphabet)
+    }
+}
+
+impl<E> Display for Run<E>
+where
+    E: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Run {}", self.0)
+    }
+}
+
+impl<E> Debug for Run<E>
+where
+    E: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Run {:?}", self.0)
+    }
+}
+
+// Operational semantics for RUN(A)
+//
+// 1) ──────────────────── a ∈ A ∖ {τ,✔}
+//     RUN(A) -a→ RUN(A)
+
+impl<E> Run<E>
+where
+    E: Clone + EventSet,
+{
+    /// Returns the events that this process can perform, which never includes τ or ✔.
+    fn alphabet<TauProof, TickProof>(&self) -> E
+    where
+        E: Tau<TauProof> + Tick<TickProof>,
+    {
+        let mut alphabet = self.0.clone();
+        alphabet.subtract(&E::tau());
+        alphabet.subtract(&E::tick());
+        alphabet
+    }
+
+    pub(crate) fn initials<TauProof, TickProof>(&self) -> E
+    where
+        E: Tau<TauProof> + Tick<TickProof>,
+    {
+        self.alphabet()
+    }
+
+    pub(crate) fn transitions<TauProof, TickProof>(
+        &self,
+        events: &E,
+    ) -> impl Iterator<Item = (E, CSP<E, TauProof, TickProof>)>
+    where
+        E: Tau<TauProof> + Tick<TickProof>,
+    {
+        // RUN(A) loops back to itself after every event, so that its state graph stays finite.
+        let mut initials = self.alphabet();
+        initials.intersect(events);
+        if initials.is_empty() {
+            return Either::Left(std::iter::empty());
+        }
+        Either::Right(std::iter::once((initials, CSP::run(self.0.clone()))))
+    }
+}
+
+#[cfg(test)]
+mod run_tests {
+    use super::*;
+
+    use maplit::hashset;
+    use proptest_attr_macro::proptest;
+
+    use crate::maximal_traces::maximal_finite_traces;
+    use crate::test_support::NumberedEvent;
+    use crate::test_support::NumberedEvents;
+    use crate::test_support::TestEvents;
+
+    #[proptest]
+    fn check_run_initials(alphabet: NumberedEvents) {
+        let alphabet = TestEvents::from(alphabet);
+        let process = CSP::<TestEvents, _, _>::run(alphabet.clone());
+        assert_eq!(process.initials(), alphabet);
+    }
+
+    #[proptest]
+    fn check_run_loops_back_to_itself(alphabet: NumberedEvents) {
+        let alphabet = TestEvents::from(alphabet);
+        let process = CSP::<TestEvents, _, _>::run(alphabet.clone());
+        for (_, after) in process.transitions(&TestEvents::universe()) {
+            assert_eq!(after, process);
+        }
+    }
+
+    #[test]
+    fn check_run_traces() {
+        let a = TestEvents::from(NumberedEvent(0));
+        let process = CSP::<TestEvents, _, _>::run(a.clone());
+        assert_eq!(maximal_finite_traces(&process), hashset! {vec![a]});
+    }
+}
+
+//-------------------------------------------------------------------------------------------------
+// Chaos
+
+#[derive(Clone, Eq, Hash, PartialEq)]
+pub(crate) struct Chaos<E>(E);
+
+impl<E> Chaos<E> {
+    pub(crate) fn new(alphabet: E) -> Chaos<E> {
+        Chaos(alphabet)
+    }
+}
+
+impl<E> Display for Chaos<E>
+where
+    E: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Chaos {}", self.0)
+    }
+}
+
+impl<E> Debug for Chaos<E>
+where
+    E: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Chaos {:?}", self.0)
+    }
+}
+
+// Operational semantics for CHAOS(A)
+//
+// 1) ──────────────────────── a ∈ A ∖ {τ,✔}
+//     CHAOS(A) -a→ CHAOS(A)
+//
+// 2) ────────────────────
+//     CHAOS(A) -τ→ Stop
+
+impl<E> Chaos<E>
+where
+    E: Clone + EventSet,
+{
+    /// Returns the visible events that this process can perform, which never includes τ or ✔.
+    fn alphabet<TauProof, TickProof>(&self) -> E
+    where
+        E: Tau<TauProof> + Tick<TickProof>,
+    {
+        let mut alphabet = self.0.clone();
+        alphabet.subtract(&E::tau());
+        alphabet.subtract(&E::tick());
+        alphabet
+    }
+
+    pub(crate) fn initials<TauProof, TickProof>(&self) -> E
+    where
+        E: Tau<TauProof> + Tick<TickProof>,
+    {
+        let mut initials = self.alphabet();
+        initials.union(&E::tau());
+        initials
+    }
+
+    pub(crate) fn transitions<TauProof, TickProof>(
+        &self,
+        events: &E,
+    ) -> impl Iterator<Item = (E, CSP<E, TauProof, TickProof>)>
+    where
+        E: Tau<TauProof> + Tick<TickProof>,
+    {
+        // CHAOS(A) can refuse everything at any point by deciding to stop.
+        let stop_transition = if events.can_perform_tau() {
+            Some((E::tau(), CSP::stop()))
+        } else {
+            None
+        };
+
+        // Or it can perform any event in A and loop back to itself.
+        let mut initials = self.alphabet();
+        initials.intersect(events);
+        let other_transition = if !initials.is_empty() {
+            Some((initials, CSP::chaos(self.0.clone())))
+        } else {
+            None
+        };
+
+        stop_transition.into_iter().chain(other_transition)
+    }
+}
+
+#[cfg(test)]
+mod chaos_tests {
+    use super::*;
+
+    use maplit::hashset;
+    use proptest_attr_macro::proptest;
+
+    use crate::maximal_traces::maximal_finite_traces;
+    use crate::test_support::NumberedEvent;
+    use crate::test_support::NumberedEvents;
+    use crate::test_support::TestEvents;
+
+    #[proptest]
+    fn check_chaos_initials(alphabet: NumberedEvents) {
+        let alphabet = TestEvents::from(alphabet);
+        let process = CSP::<TestEvents, _, _>::chaos(alphabet.clone());
+        let mut expected = alphabet;
+        expected.union(&TestEvents::tau());
+        assert_eq!(process.initials(), expected);
+    }
+
+    #[test]
+    fn check_chaos_transitions() {
+        let a = TestEvents::from(NumberedEvent(0));
+        let process = CSP::<TestEvents, _, _>::chaos(a.clone());
+        let transitions = process
+            .transitions(&TestEvents::universe())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            transitions,
+            vec![
+                (TestEvents::tau(), CSP::stop()),
+                (a.clone(), process.clone())
+            ]
+        );
+        assert_eq!(maximal_finite_traces(&process), hashset! {vec![a]});
+    }
+}
+
+//-------------------------------------------------------------------------------------------------
+// Div
+
+#[derive(Clone, Eq, Hash, PartialEq)]
+pub(crate) struct Div<E, TauProof>(PhantomData<E>, PhantomData<TauProof>);
+
+impl<E, TauProof> Div<E, TauProof> {
+    pub(crate) fn new() -> Div<E, TauProof> {
+        Div(PhantomData, PhantomData)
+    }
+}
+
+impl<E, TauProof> Display for Div<E, TauProof> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("Div")
+    }
+}
+
+impl<E, TauProof> Debug for Div<E, TauProof> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        (self as &dyn Display).fmt(f)
+    }
+}
+
+// Operational semantics for DIV
+//
+// 1) ─────────────
+//     DIV -τ→ DIV
+
+impl<E, TauProof> Div<E, TauProof>
+where
+    E: EventSet + Tau<TauProof>,
+{
+    pub(crate) fn initials(&self) -> E {
+        E::tau()
+    }
+
+    pub(crate) fn transitions<TickProof>(
+        &self,
+        events: &E,
+    ) -> impl Iterator<Item = (E, CSP<E, TauProof, TickProof>)> {
+        if !events.can_perform_tau() {
+            return Either::Left(std::iter::empty());
+        }
+
+        Either::Right(std::iter::once((E::tau(), CSP::div())))
+    }
+}
+
+#[cfg(test)]
+mod div_tests {
+    use super::*;
+
+    use maplit::hashset;
+
+    use crate::maximal_traces::maximal_finite_traces;
+    use crate::test_support::TestEvents;
+
+    #[test]
+    fn check_div_initials() {
+        let process = CSP::<TestEvents, _, _>::div();
+        assert_eq!(process.initials(), TestEvents::tau());
+    }
+
+    #[test]
+    fn check_div_loops_back_to_itself() {
+        let process = CSP::<TestEvents, _, _>::div();
+        let transitions = process
+            .transitions(&TestEvents::universe())
+            .collect::<Vec<_>>();
+        assert_eq!(transitions, vec![(TestEvents::tau(), process.clone())]);
+    }
+
+    #[test]
+    fn check_div_traces() {
+        let process = CSP::<TestEvents, _, _>::div();
+        assert_eq!(maximal_finite_traces(&process), hashset! {vec![]});
+    }
+}
@@ -0,0 +1,229 @@
+// -*- coding: utf-8 -*-
+// ------------------------------------------------------------------------------------------------
+// Copyright © 2020, HST authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License.  You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied.  See the License for the specific language governing permissions and
+// limitations under the License.
+// ------------------------------------------------------------------------------------------------
+
+//! Defines the priority (`prioritise`) operator.
+
+use std::fmt::Debug;
+use std::fmt::Display;
+use std::rc::Rc;
+
+use crate::csp::CSP;
+use crate::event::EventSet;
+use crate::primitives::Tau;
+use crate::primitives::Tick;
+use crate::probabilistic_choice::map_distribution;
+use crate::probabilistic_choice::Distribution;
+
+#[derive(Clone, Eq, Hash, PartialEq)]
+pub struct Prioritise<E, TauProof, TickProof> {
+    p: CSP<E, TauProof, TickProof>,
+    priorities: Rc<Vec<E>>,
+}
+
+impl<E, TauProof, TickProof> Debug for Prioritise<E, TauProof, TickProof>
+where
+    E: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("Prioritise")
+            .field("p", &self.p)
+            .field("priorities", &self.priorities)
+            .finish()
+    }
+}
+
+impl<E, TauProof, TickProof> Display for Prioritise<E, TauProof, TickProof>
+where
+    E: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "prioritise({}, ⟨", self.p)?;
+        for (index, events) in self.priorities.iter().enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{}", events)?;
+        }
+        f.write_str("⟩)")
+    }
+}
+
+impl<E, TauProof, TickProof> Prioritise<E, TauProof, TickProof> {
+    pub(crate) fn new(
+        p: CSP<E, TauProof, TickProof>,
+        priorities: Rc<Vec<E>>,
+    ) -> Prioritise<E, TauProof, TickProof> {
+        Prioritise { p, priorities }
+    }
+}
+
+// Operational semantics for prioritise(P, ⟨A₁, …, Aₙ⟩)
+//
+// τ and ✔ are implicitly part of A₁.  Events that aren't in any Aᵢ are unaffected by priority.
+//
+//                        P -a→ P'
+// 1)  ───────────────────────────────────────────── a ∉ A₁ ∪ … ∪ Aₙ
+//      prioritise(P, A) -a→ prioritise(P', A)
+//
+//                        P -a→ P'
+// 2)  ───────────────────────────────────────────── a ∈ Aₖ, ∄ b ∈ A₁ ∪ … ∪ Aₖ₋₁ • P -b→
+//      prioritise(P, A) -a→ prioritise(P', A)
+
+impl<E, TauProof, TickProof> Prioritise<E, TauProof, TickProof>
+where
+    E: Clone + EventSet + Tau<TauProof> + Tick<TickProof>,
+    TauProof: Clone,
+    TickProof: Clone,
+{
+    /// Returns the events at each priority level, with τ and ✔ added to the highest level, and
+    /// with each event only appearing at its highest level.
+    fn levels(&self) -> Vec<E> {
+        let mut seen = E::tau();
+        seen.union(&E::tick());
+        let mut levels = Vec::with_capacity(self.priorities.len().max(1));
+        levels.push(seen.clone());
+        for (index, priority) in self.priorities.iter().enumerate() {
+            let mut level = priority.clone();
+            level.subtract(&seen);
+            seen.union(&level);
+            if index == 0 {
+                levels[0].union(&level);
+            } else {
+                levels.push(level);
+            }
+        }
+        levels
+    }
+
+    /// Returns the events that P is allowed to perform right now: everything except the events
+    /// that are pre-empted by some higher-priority event that P can perform.
+    fn allowed(&self) -> E {
+        let p_initials = self.p.initials();
+        let levels = self.levels();
+        let mut allowed = E::universe();
+        if let Some(highest) = levels.iter().position(|level| {
+            let mut available = level.clone();
+            available.intersect(&p_initials);
+            !available.is_empty()
+        }) {
+            for level in &levels[highest + 1..] {
+                allowed.subtract(level);
+            }
+        }
+        allowed
+    }
+
+    pub(crate) fn distribution(&self) -> Option<Distribution<E, TauProof, TickProof>> {
+        let distribution = self.p.distribution()?;
+        Some(map_distribution(distribution, |p| {
+            CSP::shared_prioritise(p, self.priorities.clone())
+        }))
+    }
+
+    pub(crate) fn initials(&self) -> E {
+        let mut initials = self.p.initials();
+        initials.intersect(&self.allowed());
+        initials
+    }
+
+    pub(crate) fn transitions(
+        &self,
+        events: &E,
+    ) -> impl Iterator<Item = (E, CSP<E, TauProof, TickProof>)> + '_ {
+        let mut events = events.clone();
+        events.intersect(&self.allowed());
+        self.p.transitions(&events).map(move |(initials, after)| {
+            (
+                initials,
+                CSP::shared_prioritise(after, self.priorities.clone()),
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod prioritise_tests {
+    use super::*;
+
+    use maplit::hashset;
+    use proptest_attr_macro::proptest;
+
+    use crate::csp::CSP;
+    use crate::maximal_traces::maximal_finite_traces;
+    use crate::test_support::NumberedEvent;
+    use crate::test_support::NumberedEvents;
+    use crate::test_support::TestEvents;
+
+    #[proptest]
+    fn check_no_priorities_traces(p: CSP<TestEvents, _, _>) {
+        let process = CSP::prioritise(p.clone(), vec![]);
+        assert_eq!(maximal_finite_traces(&process), maximal_finite_traces(&p));
+    }
+
+    #[proptest]
+    fn check_prioritise_initials_are_subset(p: CSP<TestEvents, _, _>, a: NumberedEvents) {
+        // Arbitrary processes are deterministic, so they only have one initial step, which
+        // prioritise can never remove entirely.
+        let process = CSP::prioritise(p.clone(), vec![TestEvents::from(a)]);
+        let mut initials = process.initials();
+        assert_eq!(initials.is_empty(), p.initials().is_empty());
+        initials.subtract(&p.initials());
+        assert!(initials.is_empty());
+    }
+
+    #[test]
+    fn check_higher_priority_events_preempt_lower() {
+        let a = TestEvents::from(NumberedEvent(0));
+        let b = TestEvents::from(NumberedEvent(1));
+        let c = TestEvents::from(NumberedEvent(2));
+        let p = CSP::external_choice(
+            CSP::external_choice(
+                CSP::prefix(a.clone(), CSP::stop()),
+                CSP::prefix(b.clone(), CSP::stop()),
+            ),
+            CSP::prefix(c.clone(), CSP::stop()),
+        );
+        let process = CSP::prioritise(p, vec![a.clone(), b]);
+        // c isn't in any priority level, so it's not affected.
+        let mut expected = a.clone();
+        expected.union(&c);
+        assert_eq!(process.initials(), expected);
+        assert_eq!(maximal_finite_traces(&process), hashset! {vec![a], vec![c]});
+    }
+
+    #[test]
+    fn check_tau_preempts_lower_priority_events() {
+        let a = TestEvents::from(NumberedEvent(0));
+        let b = TestEvents::from(NumberedEvent(1));
+        let p = CSP::timeout(
+            CSP::prefix(b.clone(), CSP::stop()),
+            CSP::prefix(a.clone(), CSP::stop()),
+        );
+        let process = CSP::prioritise(p, vec![a.clone(), b]);
+        assert_eq!(process.initials(), TestEvents::tau());
+        assert_eq!(maximal_finite_traces(&process), hashset! {vec![a]});
+    }
+
+    #[test]
+    fn check_tau_does_not_preempt_first_level() {
+        let a = TestEvents::from(NumberedEvent(0));
+        let p = CSP::timeout(CSP::prefix(a.clone(), CSP::stop()), CSP::stop());
+        let process = CSP::prioritise(p, vec![a.clone()]);
+        let mut expected = a.clone();
+        expected.union(&TestEvents::tau());
+        assert_eq!(process.initials(), expected);
+        assert_eq!(maximal_finite_traces(&process), hashset! {vec![a]});
+    }
+}
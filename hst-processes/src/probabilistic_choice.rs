@@ -0,0 +1,249 @@
+// -*- coding: utf-8 -*-
+// ------------------------------------------------------------------------------------------------
+// Copyright © 2020, HST authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License.  You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied.  See the License for the specific language governing permissions and
+// limitations under the License.
+// ------------------------------------------------------------------------------------------------
+
+//! Defines the probabilistic choice (`[p]+`) operator.
+
+use std::fmt::Debug;
+use std::fmt::Display;
+use std::hash::Hash;
+use std::hash::Hasher;
+
+use crate::csp::CSP;
+use crate::event::EventSet;
+use crate::primitives::Tau;
+use crate::primitives::Tick;
+
+/// A probability between 0 and 1.  We need to compare and hash processes, so we compare and hash
+/// probabilities by their bit patterns.
+#[derive(Clone, Copy)]
+struct Probability(f64);
+
+impl PartialEq for Probability {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.to_bits() == other.0.to_bits()
+    }
+}
+
+impl Eq for Probability {}
+
+impl Hash for Probability {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.to_bits().hash(state);
+    }
+}
+
+/// A probability distribution over processes.  The probabilities add up to 1.
+pub type Distribution<E, TauProof, TickProof> = Vec<(f64, CSP<E, TauProof, TickProof>)>;
+
+/// Passes an operand's distribution up through the operator that contains it.  `after` constructs
+/// the process that the operator becomes, given the state that the operand moves to.
+///
+/// A probabilistic choice is resolved as soon as it's reached, so if any operand that's currently
+/// running is a probabilistic choice, then the operator is one as well, with the same
+/// probabilities.  (Operands that haven't started yet, like the `Q` in `P ; Q`, don't count.)
+pub(crate) fn map_distribution<E, TauProof, TickProof, F>(
+    distribution: Distribution<E, TauProof, TickProof>,
+    mut after: F,
+) -> Distribution<E, TauProof, TickProof>
+where
+    F: FnMut(CSP<E, TauProof, TickProof>) -> CSP<E, TauProof, TickProof>,
+{
+    distribution
+        .into_iter()
+        .map(|(probability, operand)| (probability, after(operand)))
+        .collect()
+}
+
+#[derive(Clone, Eq, Hash, PartialEq)]
+pub struct ProbabilisticChoice<E, TauProof, TickProof> {
+    p: CSP<E, TauProof, TickProof>,
+    probability: Probability,
+    q: CSP<E, TauProof, TickProof>,
+}
+
+impl<E, TauProof, TickProof> Debug for ProbabilisticChoice<E, TauProof, TickProof>
+where
+    E: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("ProbabilisticChoice")
+            .field("p", &self.p)
+            .field("probability", &self.probability.0)
+            .field("q", &self.q)
+            .finish()
+    }
+}
+
+impl<E, TauProof, TickProof> Display for ProbabilisticChoice<E, TauProof, TickProof>
+where
+    E: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} [{}]+ {}", self.p, self.probability.0, self.q)
+    }
+}
+
+impl<E, TauProof, TickProof> ProbabilisticChoice<E, TauProof, TickProof> {
+    pub(crate) fn new(
+        p: CSP<E, TauProof, TickProof>,
+        probability: f64,
+        q: CSP<E, TauProof, TickProof>,
+    ) -> ProbabilisticChoice<E, TauProof, TickProof> {
+        assert!(
+            (0.0..=1.0).contains(&probability),
+            "Probability {} is not between 0 and 1",
+            probability
+        );
+        // -0.0 and 0.0 have different bit patterns, but should give equal processes.
+        ProbabilisticChoice {
+            p,
+            probability: Probability(probability + 0.0),
+            q,
+        }
+    }
+}
+
+// Operational semantics for P [p]+ Q
+//
+//  1)  ────────────────── with probability p
+//       P [p]+ Q -τ→ P
+//
+//  2)  ────────────────── with probability 1-p
+//       P [p]+ Q -τ→ Q
+//
+// Analyses that don't care about probabilities will see this as an internal choice.  Sides that
+// have no chance of being chosen don't have a transition.
+
+impl<E, TauProof, TickProof> ProbabilisticChoice<E, TauProof, TickProof>
+where
+    E: Clone + EventSet + Tau<TauProof> + Tick<TickProof>,
+    TauProof: Clone,
+    TickProof: Clone,
+{
+    pub(crate) fn distribution(&self) -> Distribution<E, TauProof, TickProof> {
+        let p = self.probability.0;
+        let mut distribution = Vec::with_capacity(2);
+        if p > 0.0 {
+            distribution.push((p, self.p.clone()));
+        }
+        if p < 1.0 {
+            distribution.push((1.0 - p, self.q.clone()));
+        }
+        distribution
+    }
+
+    pub(crate) fn initials(&self) -> E {
+        E::tau()
+    }
+
+    pub(crate) fn transitions(
+        &self,
+        events: &E,
+    ) -> impl Iterator<Item = (E, CSP<E, TauProof, TickProof>)> {
+        let distribution = if events.can_perform_tau() {
+            self.distribution()
+        } else {
+            Vec::new()
+        };
+        distribution.into_iter().map(|(_, after)| (E::tau(), after))
+    }
+}
+
+#[cfg(test)]
+mod probabilistic_choice_tests {
+    use super::*;
+
+    use proptest_attr_macro::proptest;
+
+    use crate::csp::CSP;
+    use crate::event::Here;
+    use crate::maximal_traces::maximal_finite_traces;
+    use crate::test_support::NumberedEvent;
+    use crate::test_support::TestEvents;
+
+    #[proptest]
+    fn check_probabilistic_choice_initials(p: CSP<TestEvents, _, _>, q: CSP<TestEvents, _, _>) {
+        let process = CSP::probabilistic_choice(p, 0.5, q);
+        assert_eq!(process.initials(), TestEvents::tau());
+    }
+
+    #[proptest]
+    fn check_probabilistic_choice_traces(p: CSP<TestEvents, _, _>, q: CSP<TestEvents, _, _>) {
+        let process = CSP::probabilistic_choice(p.clone(), 0.25, q.clone());
+        assert_eq!(
+            maximal_finite_traces(&process),
+            maximal_finite_traces(&CSP::internal_choice(p, q))
+        );
+    }
+
+    #[proptest]
+    fn check_probabilistic_choice_distribution(p: CSP<TestEvents, _, _>, q: CSP<TestEvents, _, _>) {
+        let process = CSP::probabilistic_choice(p.clone(), 0.25, q.clone());
+        assert_eq!(
+            process.distribution(),
+            Some(vec![(0.25, p.clone()), (0.75, q)])
+        );
+        assert_eq!(p.distribution(), None);
+    }
+
+    #[proptest]
+    fn check_nested_distribution(
+        p: CSP<TestEvents, _, _>,
+        q: CSP<TestEvents, _, _>,
+        r: CSP<TestEvents, _, _>,
+    ) {
+        let a = TestEvents::from(NumberedEvent(0));
+        let choice = CSP::probabilistic_choice(p.clone(), 0.25, q.clone());
+        assert_eq!(
+            CSP::interleave(r.clone(), choice.clone()).distribution(),
+            Some(vec![
+                (0.25, CSP::interleave(r.clone(), p.clone())),
+                (0.75, CSP::interleave(r.clone(), q.clone())),
+            ])
+        );
+        assert_eq!(
+            CSP::sequential_composition(choice.clone(), r.clone()).distribution(),
+            Some(vec![
+                (0.25, CSP::sequential_composition(p, r.clone())),
+                (0.75, CSP::sequential_composition(q, r.clone())),
+            ])
+        );
+        // The choice hasn't started yet, so it hasn't been resolved.
+        assert_eq!(
+            CSP::sequential_composition(CSP::prefix(a, CSP::skip()), choice).distribution(),
+            None
+        );
+    }
+
+    #[proptest]
+    fn check_certain_probabilistic_choice(p: CSP<TestEvents, _, _>, q: CSP<TestEvents, _, _>) {
+        let process = CSP::probabilistic_choice(p.clone(), 1.0, q);
+        assert_eq!(process.distribution(), Some(vec![(1.0, p.clone())]));
+        assert_eq!(maximal_finite_traces(&process), maximal_finite_traces(&p));
+    }
+
+    #[proptest]
+    fn check_negative_zero_probability(p: CSP<TestEvents, _, _>, q: CSP<TestEvents, _, _>) {
+        let process = CSP::probabilistic_choice(p.clone(), -0.0, q.clone());
+        assert_eq!(process, CSP::probabilistic_choice(p, 0.0, q.clone()));
+        assert_eq!(process.distribution(), Some(vec![(1.0, q)]));
+    }
+
+    #[test]
+    #[should_panic(expected = "Probability 1.5 is not between 0 and 1")]
+    fn cannot_use_invalid_probability() {
+        CSP::<TestEvents, Here, Here>::probabilistic_choice(CSP::stop(), 1.5, CSP::skip());
+    }
+}
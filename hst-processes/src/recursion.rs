@@ -0,0 +1,472 @@
+// -*- coding: utf-8 -*-
+// ------------------------------------------------------------------------------------------------
+// Copyright © 2020, HST authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License.  You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied.  See the License for the specific language governing permissions and
+// limitations under the License.
+// ------------------------------------------------------------------------------------------------
+
+//! Defines named, recursive processes.
+
+use std::cell::RefCell;
+use std::fmt::Debug;
+use std::fmt::Display;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::rc::Rc;
+
+use crate::csp::CSP;
+use crate::event::EventSet;
+use crate::primitives::Tau;
+use crate::primitives::Tick;
+use crate::probabilistic_choice::Distribution;
+
+/// A scope that lets you define named processes that can refer to each other (and themselves)
+/// recursively.  You first create a name for each process, which lets you refer to it while
+/// defining any of the processes in the scope, and then provide the definition:
+///
+/// ```
+/// # use hst_processes::*;
+/// # // A set that can contain a single event, `a`.
+/// # #[derive(Clone, Debug, Eq, Hash, PartialEq)]
+/// # struct A(bool);
+/// # impl EventSet for A {
+/// #     fn empty() -> Self { A(false) }
+/// #     fn is_empty(&self) -> bool { !self.0 }
+/// #     fn intersect(&mut self, other: &Self) { self.0 &= other.0 }
+/// #     fn negate(&mut self) { self.0 = !self.0 }
+/// #     fn subtract(&mut self, other: &Self) { self.0 &= !other.0 }
+/// #     fn union(&mut self, other: &Self) { self.0 |= other.0 }
+/// #     fn universe() -> Self { A(true) }
+/// # }
+/// type Events = DisjointSum<PrimitiveEvents, A>;
+/// let a = Events::from_b(A(true));
+///
+/// let scope = RecursionScope::new();
+/// let p = scope.create_process("P");
+/// scope.define(p, CSP::prefix(a.clone(), scope.recurse(p)));
+/// let process = scope.recurse(p);
+///
+/// assert_eq!(process.initials(), a);
+/// assert_eq!(process.transitions(&a).next(), Some((a, process.clone())));
+/// ```
+///
+/// Each reference to a named process is a single state in the process's state graph, so recursive
+/// definitions produce cyclic, but finite, state graphs.
+///
+/// Each reference keeps its scope alive, so you can drop the scope once you've defined all of its
+/// processes (for instance, when a helper function builds a process and returns it).  But since
+/// the definitions usually contain references back to the scope, that forms a reference cycle,
+/// which is never freed on its own.  If you need to free the processes in a scope, call
+/// [`clear`][RecursionScope::clear] once you're done with them.
+pub struct RecursionScope<E, TauProof, TickProof>(Rc<ScopeInner<E, TauProof, TickProof>>);
+
+/// Identifies one of the named processes in a [`RecursionScope`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct RecursiveProcessId(usize);
+
+struct ScopeInner<E, TauProof, TickProof> {
+    names: RefCell<Vec<String>>,
+    definitions: RefCell<Vec<Option<CSP<E, TauProof, TickProof>>>>,
+    /// Whether we are currently in the middle of unfolding each process's definition, which lets
+    /// us detect unguarded recursion.
+    unfolding: RefCell<Vec<bool>>,
+}
+
+impl<E, TauProof, TickProof> RecursionScope<E, TauProof, TickProof> {
+    pub fn new() -> RecursionScope<E, TauProof, TickProof> {
+        RecursionScope(Rc::new(ScopeInner {
+            names: RefCell::new(Vec::new()),
+            definitions: RefCell::new(Vec::new()),
+            unfolding: RefCell::new(Vec::new()),
+        }))
+    }
+
+    /// Creates a new named process in this scope.  You must provide its definition (via
+    /// [`define`][RecursionScope::define]) before you can ask for its initials or transitions.
+    pub fn create_process<S>(&self, name: S) -> RecursiveProcessId
+    where
+        S: Into<String>,
+    {
+        let mut names = self.0.names.borrow_mut();
+        let id = RecursiveProcessId(names.len());
+        names.push(name.into());
+        self.0.definitions.borrow_mut().push(None);
+        self.0.unfolding.borrow_mut().push(false);
+        id
+    }
+
+    /// Provides the definition of one of the named processes in this scope.  Panics if the
+    /// process has already been defined.
+    ///
+    /// The definition must be _guarded_: it can't refer to itself unless that reference occurs
+    /// after some other event.  (`P = a → P` is fine; `P = P □ a → Stop` is not.)  We can't check
+    /// this here, since the definition might refer to other processes that haven't been defined
+    /// yet; instead, we panic the first time that we try to unfold an unguarded definition.
+    pub fn define(&self, id: RecursiveProcessId, process: CSP<E, TauProof, TickProof>) {
+        let mut definitions = self.0.definitions.borrow_mut();
+        assert!(
+            definitions[id.0].is_none(),
+            "Recursive process {} is already defined",
+            self.0.names.borrow()[id.0]
+        );
+        definitions[id.0] = Some(process);
+    }
+
+    /// Returns a process that behaves like one of the named processes in this scope.  You can
+    /// call this before the named process has been defined.
+    pub fn recurse(&self, id: RecursiveProcessId) -> CSP<E, TauProof, TickProof> {
+        CSP::recursion(Recursion {
+            scope: self.0.clone(),
+            id,
+        })
+    }
+
+    /// Drops the definitions of all of the processes in this scope, breaking the reference cycles
+    /// between the definitions and the references to them.  Afterwards, any process that refers
+    /// to this scope will panic if you use it, as if it had never been defined.
+    pub fn clear(&self) {
+        // Dropping a definition might drop references to this scope, so we make sure that we're
+        // not still borrowing the definitions when that happens.
+        let definitions = self
+            .0
+            .definitions
+            .borrow_mut()
+            .iter_mut()
+            .map(Option::take)
+            .collect::<Vec<_>>();
+        drop(definitions);
+    }
+}
+
+impl<E, TauProof, TickProof> Default for RecursionScope<E, TauProof, TickProof> {
+    fn default() -> RecursionScope<E, TauProof, TickProof> {
+        RecursionScope::new()
+    }
+}
+
+pub struct Recursion<E, TauProof, TickProof> {
+    scope: Rc<ScopeInner<E, TauProof, TickProof>>,
+    id: RecursiveProcessId,
+}
+
+impl<E, TauProof, TickProof> Clone for Recursion<E, TauProof, TickProof> {
+    fn clone(&self) -> Self {
+        Recursion {
+            scope: self.scope.clone(),
+            id: self.id,
+        }
+    }
+}
+
+// Two references are the same process if they refer to the same name in the same scope.  We
+// can't compare the definitions themselves, since they will usually contain the references!
+
+impl<E, TauProof, TickProof> PartialEq for Recursion<E, TauProof, TickProof> {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.scope, &other.scope) && self.id == other.id
+    }
+}
+
+impl<E, TauProof, TickProof> Eq for Recursion<E, TauProof, TickProof> {}
+
+impl<E, TauProof, TickProof> Hash for Recursion<E, TauProof, TickProof> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (Rc::as_ptr(&self.scope) as usize).hash(state);
+        self.id.hash(state);
+    }
+}
+
+impl<E, TauProof, TickProof> Debug for Recursion<E, TauProof, TickProof> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Recursion({})", self)
+    }
+}
+
+impl<E, TauProof, TickProof> Display for Recursion<E, TauProof, TickProof> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(&self.scope.names.borrow()[self.id.0])
+    }
+}
+
+/// Clears a process's `unfolding` flag when dropped, so that the flag is cleared even if something
+/// panics while we're unfolding the process's definition.
+struct Unfolding<'a> {
+    unfolding: &'a RefCell<Vec<bool>>,
+    index: usize,
+}
+
+impl Drop for Unfolding<'_> {
+    fn drop(&mut self) {
+        self.unfolding.borrow_mut()[self.index] = false;
+    }
+}
+
+// Operational semantics for a named process N = P
+//
+//      P -a→ P'
+// 1) ───────────
+//      N -a→ P'
+
+impl<E, TauProof, TickProof> Recursion<E, TauProof, TickProof>
+where
+    E: Clone + EventSet + Tau<TauProof> + Tick<TickProof>,
+    TauProof: Clone,
+    TickProof: Clone,
+{
+    /// Calls `f` with this process's definition.  Panics if the process hasn't been defined, or
+    /// if we're already in the middle of unfolding it — which can only happen if the definition
+    /// refers to itself without performing any events first.
+    fn unfold<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&CSP<E, TauProof, TickProof>) -> R,
+    {
+        let scope = &self.scope;
+        let definition = match &scope.definitions.borrow()[self.id.0] {
+            Some(definition) => definition.clone(),
+            None => panic!(
+                "Recursive process {} has not been defined",
+                scope.names.borrow()[self.id.0]
+            ),
+        };
+        let already_unfolding =
+            std::mem::replace(&mut scope.unfolding.borrow_mut()[self.id.0], true);
+        if already_unfolding {
+            panic!(
+                "Recursive process {} is not guarded",
+                scope.names.borrow()[self.id.0]
+            );
+        }
+        let _unfolding = Unfolding {
+            unfolding: &scope.unfolding,
+            index: self.id.0,
+        };
+        f(&definition)
+    }
+
+    pub(crate) fn distribution(&self) -> Option<Distribution<E, TauProof, TickProof>> {
+        self.unfold(|definition| definition.distribution())
+    }
+
+    pub(crate) fn initials(&self) -> E {
+        self.unfold(|definition| definition.initials())
+    }
+
+    pub(crate) fn transitions(
+        &self,
+        events: &E,
+    ) -> impl Iterator<Item = (E, CSP<E, TauProof, TickProof>)> {
+        // We've cloned the definition out of the scope, so we can't return an iterator that
+        // borrows from it.
+        self.unfold(|definition| definition.transitions(events).collect::<Vec<_>>())
+            .into_iter()
+    }
+}
+
+#[cfg(test)]
+mod recursion_tests {
+    use super::*;
+
+    use std::panic::AssertUnwindSafe;
+
+    use maplit::hashset;
+
+    use crate::csp::CSP;
+    use crate::event::Here;
+    use crate::maximal_traces::maximal_finite_traces;
+    use crate::test_support::NumberedEvent;
+    use crate::test_support::TestEvents;
+
+    #[test]
+    fn check_recursive_process_initials() {
+        let a = TestEvents::from(NumberedEvent(0));
+        let scope = RecursionScope::new();
+        let p = scope.create_process("P");
+        scope.define(p, CSP::prefix(a.clone(), scope.recurse(p)));
+        let process = scope.recurse(p);
+        assert_eq!(process.initials(), a);
+    }
+
+    #[test]
+    fn check_recursive_process_loops_back_to_itself() {
+        let a = TestEvents::from(NumberedEvent(0));
+        let scope = RecursionScope::new();
+        let p = scope.create_process("P");
+        scope.define(p, CSP::prefix(a.clone(), scope.recurse(p)));
+        let process = scope.recurse(p);
+        let transitions = process.transitions(&a).collect::<Vec<_>>();
+        assert_eq!(transitions, vec![(a, process.clone())]);
+    }
+
+    #[test]
+    fn check_recursive_process_traces() {
+        let a = TestEvents::from(NumberedEvent(0));
+        let b = TestEvents::from(NumberedEvent(1));
+        let scope = RecursionScope::new();
+        let p = scope.create_process("P");
+        scope.define(
+            p,
+            CSP::external_choice(
+                CSP::prefix(a.clone(), scope.recurse(p)),
+                CSP::prefix(b.clone(), CSP::skip()),
+            ),
+        );
+        let process = scope.recurse(p);
+        assert_eq!(
+            maximal_finite_traces(&process),
+            hashset! {vec![a], vec![b, TestEvents::tick()]}
+        );
+    }
+
+    #[test]
+    fn check_mutually_recursive_process_traces() {
+        let a = TestEvents::from(NumberedEvent(0));
+        let b = TestEvents::from(NumberedEvent(1));
+        let scope = RecursionScope::new();
+        let p = scope.create_process("P");
+        let q = scope.create_process("Q");
+        scope.define(p, CSP::prefix(a.clone(), scope.recurse(q)));
+        scope.define(q, CSP::prefix(b.clone(), scope.recurse(p)));
+        let process = scope.recurse(p);
+        assert_eq!(maximal_finite_traces(&process), hashset! {vec![a, b]});
+    }
+
+    #[test]
+    fn check_recursive_process_display() {
+        let scope = RecursionScope::<TestEvents, Here, Here>::new();
+        let p = scope.create_process("P");
+        scope.define(p, CSP::prefix(NumberedEvent(0).into(), scope.recurse(p)));
+        assert_eq!(scope.recurse(p).to_string(), "P");
+    }
+
+    #[test]
+    #[should_panic(expected = "Recursive process P has not been defined")]
+    fn cannot_use_undefined_process() {
+        let scope = RecursionScope::<TestEvents, _, _>::new();
+        let p = scope.create_process("P");
+        scope.recurse(p).initials();
+    }
+
+    #[test]
+    #[should_panic(expected = "Recursive process P is already defined")]
+    fn cannot_define_process_twice() {
+        let scope = RecursionScope::<TestEvents, Here, Here>::new();
+        let p = scope.create_process("P");
+        scope.define(p, CSP::stop());
+        scope.define(p, CSP::skip());
+    }
+
+    #[test]
+    #[should_panic(expected = "Recursive process P is not guarded")]
+    fn cannot_use_unguarded_process() {
+        let scope = RecursionScope::<TestEvents, _, _>::new();
+        let p = scope.create_process("P");
+        scope.define(p, scope.recurse(p));
+        scope.recurse(p).initials();
+    }
+
+    #[test]
+    #[should_panic(expected = "Recursive process Q is not guarded")]
+    fn cannot_use_mutually_unguarded_processes() {
+        let scope = RecursionScope::<TestEvents, _, _>::new();
+        let p = scope.create_process("P");
+        let q = scope.create_process("Q");
+        scope.define(
+            p,
+            CSP::external_choice(
+                scope.recurse(q),
+                CSP::prefix(NumberedEvent(0).into(), CSP::stop()),
+            ),
+        );
+        scope.define(q, scope.recurse(p));
+        scope
+            .recurse(q)
+            .transitions(&TestEvents::universe())
+            .count();
+    }
+
+    #[test]
+    fn check_guarded_process_can_be_unfolded_repeatedly() {
+        // The same process can appear several times in a definition, as long as each occurrence
+        // is guarded.
+        let a = TestEvents::from(NumberedEvent(0));
+        let scope = RecursionScope::new();
+        let p = scope.create_process("P");
+        scope.define(
+            p,
+            CSP::interleave(
+                CSP::prefix(a.clone(), scope.recurse(p)),
+                CSP::prefix(a.clone(), scope.recurse(p)),
+            ),
+        );
+        let process = scope.recurse(p);
+        assert_eq!(process.initials(), a);
+        assert_eq!(process.initials(), a);
+    }
+
+    #[test]
+    fn check_process_can_be_unfolded_after_panic() {
+        // P = Q, where Q isn't defined until after we've tried (and failed) to unfold P.
+        let a = TestEvents::from(NumberedEvent(0));
+        let scope = RecursionScope::new();
+        let p = scope.create_process("P");
+        let q = scope.create_process("Q");
+        scope.define(p, scope.recurse(q));
+        let result = std::panic::catch_unwind(AssertUnwindSafe(|| scope.recurse(p).initials()));
+        assert!(result.is_err());
+        scope.define(q, CSP::prefix(a.clone(), CSP::stop()));
+        assert_eq!(scope.recurse(p).initials(), a);
+    }
+
+    fn ticker(a: TestEvents) -> CSP<TestEvents, Here, Here> {
+        // P = a → P
+        let scope = RecursionScope::new();
+        let p = scope.create_process("P");
+        scope.define(p, CSP::prefix(a, scope.recurse(p)));
+        scope.recurse(p)
+    }
+
+    #[test]
+    fn check_process_outlives_scope() {
+        let a = TestEvents::from(NumberedEvent(0));
+        let process = ticker(a.clone());
+        assert_eq!(process.initials(), a);
+        assert_eq!(
+            process.transitions(&a).collect::<Vec<_>>(),
+            vec![(a, process.clone())]
+        );
+        assert_eq!(process.to_string(), "P");
+    }
+
+    #[test]
+    fn check_cleared_scope_is_freed() {
+        let scope = RecursionScope::<TestEvents, Here, Here>::new();
+        let p = scope.create_process("P");
+        scope.define(p, CSP::prefix(NumberedEvent(0).into(), scope.recurse(p)));
+        let process = scope.recurse(p);
+        let inner = Rc::downgrade(&scope.0);
+        scope.clear();
+        drop(scope);
+        assert_eq!(process.to_string(), "P");
+        drop(process);
+        assert!(inner.upgrade().is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "Recursive process P has not been defined")]
+    fn cannot_use_process_after_scope_is_cleared() {
+        let scope = RecursionScope::<TestEvents, _, _>::new();
+        let p = scope.create_process("P");
+        scope.define(p, CSP::prefix(NumberedEvent(0).into(), scope.recurse(p)));
+        let process = scope.recurse(p);
+        scope.clear();
+        process.initials();
+    }
+}
@@ -0,0 +1,648 @@
+// -*- coding: utf-8 -*-
+// ------------------------------------------------------------------------------------------------
+// Copyright © 2020, HST authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License.  You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied.  See the License for the specific language governing permissions and
+// limitations under the License.
+// ------------------------------------------------------------------------------------------------
+
+//! Checks whether one process refines another.
+//!
+//! We normalize the specification on the fly: each normalized state is the set of specification
+//! states that the specification might be in after some visible trace (closed under τ).  We then
+//! explore the product of the normalized specification and the implementation, breadth-first in
+//! the length of the visible trace, so that any counterexample we find is as short as possible.
+//! We work with the same symbolic event sets as `transitions`, so we never have to enumerate the
+//! individual events in an event set.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::hash::Hash;
+use std::hash::Hasher;
+
+use crate::csp::CSP;
+use crate::divergence::Divergence;
+use crate::divergence::TauCycles;
+use crate::event::EventSet;
+use crate::primitives::Tau;
+use crate::primitives::Tick;
+use crate::search::Search;
+
+/// Describes why an implementation does not refine a specification.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Counterexample<E> {
+    /// The implementation can perform a trace that the specification cannot.  Each element of the
+    /// trace is a set of events, any of which can occur at that point.  The implementation can
+    /// perform every trace that you get by choosing one event from each set.  The specification
+    /// can perform all but the last step of any of those traces, but cannot perform any of the
+    /// events in the last set.
+    Trace(Vec<E>),
+    /// After performing `trace`, the implementation can reach a stable state where it refuses
+    /// every event in `refusal`, but the specification has no stable state that refuses that
+    /// many events.
+    Refusal { trace: Vec<E>, refusal: E },
+    /// The implementation can diverge after a trace where the specification cannot.
+    Divergence(Divergence<E>),
+}
+
+/// Checks whether `implementation` refines `spec` in the traces model — that is, whether every
+/// trace of `implementation` is also a trace of `spec`.  If not, returns the shortest trace that
+/// `implementation` can perform but `spec` cannot.
+pub fn refines_traces<E, TauProof, TickProof>(
+    spec: &CSP<E, TauProof, TickProof>,
+    implementation: &CSP<E, TauProof, TickProof>,
+) -> Result<(), Counterexample<E>>
+where
+    E: Clone + Eq + EventSet + Hash + Tau<TauProof> + Tick<TickProof>,
+    TauProof: Clone + Eq + Hash,
+    TickProof: Clone + Eq + Hash,
+{
+    Checker::new(spec, Model::Traces).check(implementation)
+}
+
+/// Checks whether `implementation` refines `spec` in the stable failures model — that is, whether
+/// every trace of `implementation` is also a trace of `spec`, and whenever `implementation` can
+/// stably refuse a set of events after some trace, `spec` can refuse them after that trace too.
+/// If not, returns the shortest trace that leads to a violation.
+pub fn refines_failures<E, TauProof, TickProof>(
+    spec: &CSP<E, TauProof, TickProof>,
+    implementation: &CSP<E, TauProof, TickProof>,
+) -> Result<(), Counterexample<E>>
+where
+    E: Clone + Eq + EventSet + Hash + Tau<TauProof> + Tick<TickProof>,
+    TauProof: Clone + Eq + Hash,
+    TickProof: Clone + Eq + Hash,
+{
+    Checker::new(spec, Model::Failures).check(implementation)
+}
+
+/// Checks whether `implementation` refines `spec` in the failures-divergences model — that is,
+/// whether `implementation` can only diverge after traces where `spec` can diverge, and otherwise
+/// satisfies the same conditions as in the stable failures model.  Once `spec` can diverge, it
+/// acts like `CHAOS`, and places no further constraints on `implementation`.  If the refinement
+/// doesn't hold, returns the shortest trace that leads to a violation.
+pub fn refines_failures_divergences<E, TauProof, TickProof>(
+    spec: &CSP<E, TauProof, TickProof>,
+    implementation: &CSP<E, TauProof, TickProof>,
+) -> Result<(), Counterexample<E>>
+where
+    E: Clone + Display + Eq + EventSet + Hash + Tau<TauProof> + Tick<TickProof>,
+    TauProof: Clone + Eq + Hash,
+    TickProof: Clone + Eq + Hash,
+{
+    Checker::new(spec, Model::FailuresDivergences(Divergence::new)).check(implementation)
+}
+
+/// Returns whether a process is stable — that is, whether it cannot perform any τ events.  We
+/// only care about what a process refuses in its stable states, since an unstable process can
+/// always move to another state without the environment's help.
+pub(crate) fn is_stable<E, TauProof>(initials: &E) -> bool
+where
+    E: Tau<TauProof>,
+{
+    !initials.can_perform_tau()
+}
+
+/// Returns the largest set of events that a stable process with the given initials can refuse.
+pub(crate) fn refusal<E, TauProof>(initials: &E) -> E
+where
+    E: Clone + EventSet + Tau<TauProof>,
+{
+    let mut refusal = initials.clone();
+    refusal.negate();
+    refusal.subtract(&E::tau());
+    refusal
+}
+
+/// The semantic model that we're checking refinement in.
+enum Model<E, TauProof, TickProof> {
+    Traces,
+    Failures,
+    /// Also checks for divergences, using this function to describe any that we find.  (That's
+    /// the only time that we need to display a process, so the other models don't require events
+    /// to implement `Display`.)
+    FailuresDivergences(NewDivergence<E, TauProof, TickProof>),
+}
+
+type NewDivergence<E, TauProof, TickProof> =
+    fn(Vec<E>, &[CSP<E, TauProof, TickProof>]) -> Divergence<E>;
+
+/// A normalized state: the set of states that a process might be in after some trace.  The hash
+/// of a set can't depend on the order in which we visit its elements, so we combine the hashes of
+/// the elements with a commutative operation.
+#[derive(Clone)]
+struct NormalNode<E, TauProof, TickProof>(HashSet<CSP<E, TauProof, TickProof>>);
+
+impl<E, TauProof, TickProof> PartialEq for NormalNode<E, TauProof, TickProof>
+where
+    E: Eq + Hash,
+    TauProof: Eq + Hash,
+    TickProof: Eq + Hash,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<E, TauProof, TickProof> Eq for NormalNode<E, TauProof, TickProof>
+where
+    E: Eq + Hash,
+    TauProof: Eq + Hash,
+    TickProof: Eq + Hash,
+{
+}
+
+impl<E, TauProof, TickProof> Hash for NormalNode<E, TauProof, TickProof>
+where
+    E: Hash,
+    TauProof: Hash,
+    TickProof: Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let mut combined: u64 = 0;
+        for process in &self.0 {
+            let mut hasher = DefaultHasher::new();
+            process.hash(&mut hasher);
+            combined = combined.wrapping_add(hasher.finish());
+        }
+        self.0.len().hash(state);
+        combined.hash(state);
+    }
+}
+
+/// Normalizes a process on the fly.  Each normalized state is identified by its index in the order
+/// that we discovered it.
+pub(crate) struct Normalization<E, TauProof, TickProof> {
+    nodes: Vec<NormalNode<E, TauProof, TickProof>>,
+    node_ids: HashMap<NormalNode<E, TauProof, TickProof>, usize>,
+    tau_cycles: TauCycles<E, TauProof, TickProof>,
+}
+
+// See the comment on `CSP` for why processes are safe to use as keys.
+#[allow(clippy::mutable_key_type)]
+impl<E, TauProof, TickProof> Normalization<E, TauProof, TickProof>
+where
+    E: Clone + Eq + EventSet + Hash + Tau<TauProof> + Tick<TickProof>,
+    TauProof: Clone + Eq + Hash,
+    TickProof: Clone + Eq + Hash,
+{
+    pub(crate) fn new() -> Normalization<E, TauProof, TickProof> {
+        Normalization {
+            nodes: Vec::new(),
+            node_ids: HashMap::new(),
+            tau_cycles: TauCycles::new(),
+        }
+    }
+
+    /// Returns the ID of the normalized state containing `processes` and every state reachable
+    /// from them via τ.
+    pub(crate) fn node(&mut self, processes: Vec<CSP<E, TauProof, TickProof>>) -> usize {
+        let mut closure = HashSet::new();
+        let mut queue = processes;
+        while let Some(process) = queue.pop() {
+            if closure.contains(&process) {
+                continue;
+            }
+            for (_, after) in process.transitions(&E::tau()) {
+                queue.push(after);
+            }
+            closure.insert(process);
+        }
+        let node = NormalNode(closure);
+        if let Some(id) = self.node_ids.get(&node) {
+            return *id;
+        }
+        let id = self.nodes.len();
+        self.nodes.push(node.clone());
+        self.node_ids.insert(node, id);
+        id
+    }
+
+    /// Returns the states in a normalized state.
+    pub(crate) fn processes(
+        &self,
+        node: usize,
+    ) -> impl Iterator<Item = &CSP<E, TauProof, TickProof>> + '_ {
+        self.nodes[node].0.iter()
+    }
+
+    /// Splits `events` (which must not contain τ) according to which states the normalized state
+    /// `node` moves to.  Returns a separate set of events for each distinct successor, along with
+    /// any events that the process can't perform at all.
+    pub(crate) fn after(&mut self, node: usize, events: &E) -> (Vec<(E, usize)>, E) {
+        let moves = self.nodes[node]
+            .0
+            .iter()
+            .flat_map(|process| process.transitions(events))
+            .collect::<Vec<_>>();
+
+        let mut covered = E::empty();
+        for (initials, _) in &moves {
+            covered.union(initials);
+        }
+        let mut uncovered = events.clone();
+        uncovered.subtract(&covered);
+
+        // Refine the covered events into classes, where all of the events in each class lead to
+        // the same set of states.
+        let mut classes = vec![(covered, Vec::new())];
+        for (index, (initials, _)) in moves.iter().enumerate() {
+            let mut refined = Vec::with_capacity(classes.len());
+            for (class, members) in classes {
+                let mut inside = class.clone();
+                inside.intersect(initials);
+                let mut outside = class;
+                outside.subtract(initials);
+                if !inside.is_empty() {
+                    let mut members = members.clone();
+                    members.push(index);
+                    refined.push((inside, members));
+                }
+                if !outside.is_empty() {
+                    refined.push((outside, members));
+                }
+            }
+            classes = refined;
+        }
+
+        let successors = classes
+            .into_iter()
+            .filter(|(_, members)| !members.is_empty())
+            .map(|(class, members)| {
+                let processes = members
+                    .into_iter()
+                    .map(|index| moves[index].1.clone())
+                    .collect();
+                (class, self.node(processes))
+            })
+            .collect();
+        (successors, uncovered)
+    }
+
+    /// Returns a cycle of τ transitions that the normalized state `node` can diverge along, or
+    /// `None` if it cannot diverge.  Each normalized state is closed under τ, so it can diverge if
+    /// any of its states can.
+    pub(crate) fn divergence(&mut self, node: usize) -> Option<Vec<CSP<E, TauProof, TickProof>>> {
+        let tau_cycles = &mut self.tau_cycles;
+        self.nodes[node]
+            .0
+            .iter()
+            .find_map(|process| tau_cycles.find(process))
+    }
+
+    /// Returns the cache that we use to find τ cycles, so that other processes that we check
+    /// alongside this one can share what we've learned about which states can't diverge.
+    pub(crate) fn tau_cycles(&mut self) -> &mut TauCycles<E, TauProof, TickProof> {
+        &mut self.tau_cycles
+    }
+}
+
+/// A state in the product of the normalized specification and the implementation.
+type Pair<E, TauProof, TickProof> = (usize, CSP<E, TauProof, TickProof>);
+
+struct Checker<E, TauProof, TickProof> {
+    model: Model<E, TauProof, TickProof>,
+    normalization: Normalization<E, TauProof, TickProof>,
+    initial_node: usize,
+}
+
+impl<E, TauProof, TickProof> Checker<E, TauProof, TickProof>
+where
+    E: Clone + Eq + EventSet + Hash + Tau<TauProof> + Tick<TickProof>,
+    TauProof: Clone + Eq + Hash,
+    TickProof: Clone + Eq + Hash,
+{
+    fn new(
+        spec: &CSP<E, TauProof, TickProof>,
+        model: Model<E, TauProof, TickProof>,
+    ) -> Checker<E, TauProof, TickProof> {
+        let mut normalization = Normalization::new();
+        let initial_node = normalization.node(vec![spec.clone()]);
+        Checker {
+            model,
+            normalization,
+            initial_node,
+        }
+    }
+
+    /// Returns whether the normalized specification state `node` has a stable state that can
+    /// refuse everything that a stable implementation state with the given initials can refuse.
+    /// A stable state refuses everything except its initials, so that holds if the specification
+    /// state's initials are a subset of the implementation's.
+    fn can_refuse(&self, node: usize, initials: &E) -> bool {
+        self.normalization.processes(node).any(|process| {
+            let mut spec_initials = process.initials();
+            if !is_stable(&spec_initials) {
+                return false;
+            }
+            spec_initials.subtract(initials);
+            spec_initials.is_empty()
+        })
+    }
+
+    fn check(
+        &mut self,
+        implementation: &CSP<E, TauProof, TickProof>,
+    ) -> Result<(), Counterexample<E>> {
+        let mut search: Search<Pair<E, TauProof, TickProof>, E> =
+            Search::new((self.initial_node, implementation.clone()));
+        while let Some(index) = search.next() {
+            let (node, process) = search.state(index).clone();
+            if let Model::FailuresDivergences(new_divergence) = self.model {
+                // Once the specification can diverge, it acts like CHAOS, and the implementation
+                // can do anything it wants.
+                if self.normalization.divergence(node).is_some() {
+                    continue;
+                }
+                if let Some(cycle) = self.normalization.tau_cycles().find(&process) {
+                    return Err(Counterexample::Divergence(new_divergence(
+                        search.trace(index),
+                        &cycle,
+                    )));
+                }
+            }
+
+            let initials = process.initials();
+            if !matches!(self.model, Model::Traces)
+                && is_stable(&initials)
+                && !self.can_refuse(node, &initials)
+            {
+                return Err(Counterexample::Refusal {
+                    trace: search.trace(index),
+                    refusal: refusal(&initials),
+                });
+            }
+
+            for (mut initials, after) in process.transitions(&initials) {
+                // The specification doesn't care about the implementation's τ events.
+                if initials.can_perform_tau() {
+                    initials.subtract(&E::tau());
+                    search.add_edge(index, None, (node, after.clone()));
+                }
+                if initials.is_empty() {
+                    continue;
+                }
+
+                let (successors, uncovered) = self.normalization.after(node, &initials);
+                if !uncovered.is_empty() {
+                    let mut trace = search.trace(index);
+                    trace.push(uncovered);
+                    return Err(Counterexample::Trace(trace));
+                }
+                for (events, successor) in successors {
+                    search.add_edge(index, Some(events), (successor, after.clone()));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod refinement_tests {
+    use super::*;
+
+    use proptest_attr_macro::proptest;
+
+    use crate::recursion::RecursionScope;
+    use crate::test_support::event;
+    use crate::test_support::TestEvents;
+
+    #[proptest]
+    fn check_process_refines_itself_in_traces(p: CSP<TestEvents, _, _>) {
+        assert_eq!(refines_traces(&p, &p), Ok(()));
+    }
+
+    #[proptest]
+    fn check_external_choice_traces_refinement(p: CSP<TestEvents, _, _>, q: CSP<TestEvents, _, _>) {
+        let choice = CSP::external_choice(p.clone(), q);
+        assert_eq!(refines_traces(&choice, &p), Ok(()));
+    }
+
+    #[proptest]
+    fn check_everything_refines_stop_in_traces(p: CSP<TestEvents, _, _>) {
+        assert_eq!(refines_traces(&p, &CSP::stop()), Ok(()));
+    }
+
+    #[test]
+    fn check_traces_counterexample() {
+        let spec = CSP::prefix(event(0), CSP::prefix(event(1), CSP::stop()));
+        let implementation = CSP::prefix(event(0), CSP::prefix(event(2), CSP::stop()));
+        assert_eq!(
+            refines_traces(&spec, &implementation),
+            Err(Counterexample::Trace(vec![event(0), event(2)]))
+        );
+    }
+
+    #[test]
+    fn check_traces_counterexample_is_shortest() {
+        let spec = CSP::external_choice(
+            CSP::prefix(
+                event(0),
+                CSP::prefix(event(0), CSP::prefix(event(0), CSP::stop())),
+            ),
+            CSP::prefix(event(1), CSP::stop()),
+        );
+        let implementation = CSP::external_choice(
+            CSP::prefix(
+                event(0),
+                CSP::prefix(
+                    event(0),
+                    CSP::prefix(event(0), CSP::prefix(event(2), CSP::stop())),
+                ),
+            ),
+            CSP::prefix(event(1), CSP::prefix(event(2), CSP::stop())),
+        );
+        assert_eq!(
+            refines_traces(&spec, &implementation),
+            Err(Counterexample::Trace(vec![event(1), event(2)]))
+        );
+    }
+
+    #[test]
+    fn check_hidden_events_are_ignored() {
+        let spec = CSP::prefix(event(1), CSP::stop());
+        let implementation = CSP::hide(
+            CSP::prefix(event(0), CSP::prefix(event(1), CSP::stop())),
+            event(0),
+        );
+        assert_eq!(refines_traces(&spec, &implementation), Ok(()));
+        assert_eq!(
+            refines_traces(&CSP::stop(), &implementation),
+            Err(Counterexample::Trace(vec![event(1)]))
+        );
+    }
+
+    #[test]
+    fn check_nondeterministic_spec() {
+        // a → b → Stop ⊓ a → c → Stop allows a → (b → Stop □ c → Stop)
+        let spec = CSP::internal_choice(
+            CSP::prefix(event(0), CSP::prefix(event(1), CSP::stop())),
+            CSP::prefix(event(0), CSP::prefix(event(2), CSP::stop())),
+        );
+        let implementation = CSP::prefix(
+            event(0),
+            CSP::external_choice(
+                CSP::prefix(event(1), CSP::stop()),
+                CSP::prefix(event(2), CSP::stop()),
+            ),
+        );
+        assert_eq!(refines_traces(&spec, &implementation), Ok(()));
+        assert_eq!(refines_traces(&implementation, &spec), Ok(()));
+    }
+
+    #[test]
+    fn check_recursive_traces_refinement() {
+        let mut ab = event(0);
+        ab.union(&event(1));
+        let spec = CSP::run(ab);
+        let scope = RecursionScope::new();
+        let p = scope.create_process("P");
+        scope.define(
+            p,
+            CSP::prefix(event(0), CSP::prefix(event(1), scope.recurse(p))),
+        );
+        assert_eq!(refines_traces(&spec, &scope.recurse(p)), Ok(()));
+
+        let q = scope.create_process("Q");
+        scope.define(
+            q,
+            CSP::prefix(
+                event(0),
+                CSP::prefix(event(1), CSP::prefix(event(2), scope.recurse(q))),
+            ),
+        );
+        assert_eq!(
+            refines_traces(&spec, &scope.recurse(q)),
+            Err(Counterexample::Trace(vec![event(0), event(1), event(2)]))
+        );
+    }
+
+    #[proptest]
+    fn check_process_refines_itself_in_failures(p: CSP<TestEvents, _, _>) {
+        assert_eq!(refines_failures(&p, &p), Ok(()));
+    }
+
+    #[proptest]
+    fn check_internal_choice_failures_refinement(
+        p: CSP<TestEvents, _, _>,
+        q: CSP<TestEvents, _, _>,
+    ) {
+        let choice = CSP::internal_choice(p.clone(), q);
+        assert_eq!(refines_failures(&choice, &p), Ok(()));
+    }
+
+    #[test]
+    fn check_failures_counterexample() {
+        // a → Stop ⊓ Stop is allowed to refuse a, but a → Stop is not.
+        let spec = CSP::prefix(event(0), CSP::stop());
+        let implementation = CSP::internal_choice(CSP::prefix(event(0), CSP::stop()), CSP::stop());
+        assert_eq!(refines_traces(&spec, &implementation), Ok(()));
+        assert_eq!(
+            refines_failures(&spec, &implementation),
+            Err(Counterexample::Refusal {
+                trace: vec![],
+                refusal: refusal(&TestEvents::empty()),
+            })
+        );
+        assert_eq!(refines_failures(&implementation, &spec), Ok(()));
+    }
+
+    #[test]
+    fn check_failures_counterexample_after_trace() {
+        // After a, the spec must accept both b and c, but the implementation might only accept b.
+        let spec = CSP::prefix(
+            event(0),
+            CSP::external_choice(
+                CSP::prefix(event(1), CSP::stop()),
+                CSP::prefix(event(2), CSP::stop()),
+            ),
+        );
+        let implementation = CSP::internal_choice(
+            CSP::prefix(event(0), CSP::prefix(event(1), CSP::stop())),
+            CSP::prefix(event(0), CSP::prefix(event(2), CSP::stop())),
+        );
+        assert_eq!(refines_traces(&spec, &implementation), Ok(()));
+        // Either branch of the implementation's choice is a valid counterexample.
+        match refines_failures(&spec, &implementation) {
+            Err(Counterexample::Refusal {
+                trace,
+                refusal: refused,
+            }) => {
+                assert_eq!(trace, vec![event(0)]);
+                assert!(refused == refusal(&event(1)) || refused == refusal(&event(2)));
+            }
+            result => panic!("Unexpected result {:?}", result),
+        }
+    }
+
+    #[proptest]
+    fn check_process_refines_itself_in_failures_divergences(p: CSP<TestEvents, _, _>) {
+        assert_eq!(refines_failures_divergences(&p, &p), Ok(()));
+    }
+
+    #[proptest]
+    fn check_everything_refines_div_in_failures_divergences(p: CSP<TestEvents, _, _>) {
+        assert_eq!(refines_failures_divergences(&CSP::div(), &p), Ok(()));
+    }
+
+    #[test]
+    fn check_divergence_counterexample() {
+        // a → (P \ {b}), where P = b → P, diverges after a.
+        let scope = RecursionScope::new();
+        let p = scope.create_process("P");
+        scope.define(p, CSP::prefix(event(1), scope.recurse(p)));
+        let implementation = CSP::prefix(event(0), CSP::hide(scope.recurse(p), event(1)));
+        let spec = CSP::prefix(event(0), CSP::stop());
+        assert_eq!(refines_failures(&spec, &implementation), Ok(()));
+        match refines_failures_divergences(&spec, &implementation) {
+            Err(Counterexample::Divergence(divergence)) => {
+                assert_eq!(divergence.trace, vec![event(0)]);
+                assert_eq!(divergence.cycle.len(), 1);
+            }
+            result => panic!("Unexpected result {:?}", result),
+        }
+    }
+
+    #[test]
+    fn check_divergent_spec_acts_like_chaos() {
+        // Once the spec diverges after a, the implementation can do anything, including diverge.
+        let spec = CSP::prefix(event(0), CSP::div());
+        let implementation = CSP::prefix(
+            event(0),
+            CSP::external_choice(CSP::prefix(event(2), CSP::stop()), CSP::div()),
+        );
+        assert_eq!(refines_failures_divergences(&spec, &implementation), Ok(()));
+        let implementation = CSP::external_choice(
+            CSP::prefix(event(0), CSP::stop()),
+            CSP::prefix(event(1), CSP::stop()),
+        );
+        assert_eq!(
+            refines_failures_divergences(&spec, &implementation),
+            Err(Counterexample::Trace(vec![event(1)]))
+        );
+    }
+
+    #[test]
+    fn check_partial_event_sets() {
+        // The implementation can perform either a or b, but the spec can only perform a, so the
+        // counterexample should only mention b.
+        let mut ab = event(0);
+        ab.union(&event(1));
+        let spec = CSP::prefix(event(0), CSP::stop());
+        let implementation = CSP::prefix(ab, CSP::stop());
+        assert_eq!(
+            refines_traces(&spec, &implementation),
+            Err(Counterexample::Trace(vec![event(1)]))
+        );
+    }
+}
@@ -0,0 +1,259 @@
+// -*- coding: utf-8 -*-
+// ------------------------------------------------------------------------------------------------
+// Copyright © 2020, HST authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License.  You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied.  See the License for the specific language governing permissions and
+// limitations under the License.
+// ------------------------------------------------------------------------------------------------
+
+//! Defines the renaming (`[[R]]`) operator.
+
+use std::fmt::Debug;
+use std::fmt::Display;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::rc::Rc;
+
+use crate::csp::CSP;
+use crate::event::EventSet;
+use crate::primitives::Tau;
+use crate::primitives::Tick;
+use crate::probabilistic_choice::map_distribution;
+use crate::probabilistic_choice::Distribution;
+
+/// A renaming relation between events.  Each event can be renamed to any number of events, and
+/// any number of events can be renamed to the same event.  Since we work with sets of events,
+/// you describe the relation by how it maps a whole set of events, in either direction.
+///
+/// An event that the relation doesn't mention should be renamed to itself.  You don't have to
+/// worry about τ and ✔; the renaming operator never passes those to the relation, and never
+/// renames them.
+pub trait Renaming<E>: Display {
+    /// Returns the set of events that any event in `events` is renamed to.
+    fn image(&self, events: &E) -> E;
+
+    /// Returns the set of events that are renamed to any event in `events`.
+    fn preimage(&self, events: &E) -> E;
+}
+
+#[derive(Clone)]
+pub struct Rename<E, TauProof, TickProof> {
+    p: CSP<E, TauProof, TickProof>,
+    renaming: Rc<dyn Renaming<E>>,
+}
+
+// We can't compare or hash arbitrary renaming relations, so we treat two renamings as the same if
+// they are literally the same object.  Every state that a renamed process can reach shares the
+// original renaming, so this is enough to detect when we've returned to an earlier state.
+
+impl<E, TauProof, TickProof> PartialEq for Rename<E, TauProof, TickProof>
+where
+    E: PartialEq,
+    TauProof: PartialEq,
+    TickProof: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.p == other.p && Rc::ptr_eq(&self.renaming, &other.renaming)
+    }
+}
+
+impl<E, TauProof, TickProof> Eq for Rename<E, TauProof, TickProof>
+where
+    E: Eq,
+    TauProof: Eq,
+    TickProof: Eq,
+{
+}
+
+impl<E, TauProof, TickProof> Hash for Rename<E, TauProof, TickProof>
+where
+    E: Hash,
+    TauProof: Hash,
+    TickProof: Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.p.hash(state);
+        (Rc::as_ptr(&self.renaming) as *const () as usize).hash(state);
+    }
+}
+
+impl<E, TauProof, TickProof> Debug for Rename<E, TauProof, TickProof>
+where
+    E: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("Rename")
+            .field("p", &self.p)
+            .field("renaming", &format_args!("{}", self.renaming))
+            .finish()
+    }
+}
+
+impl<E, TauProof, TickProof> Display for Rename<E, TauProof, TickProof>
+where
+    E: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}[[{}]]", self.p, self.renaming)
+    }
+}
+
+impl<E, TauProof, TickProof> Rename<E, TauProof, TickProof> {
+    pub(crate) fn new(
+        p: CSP<E, TauProof, TickProof>,
+        renaming: Rc<dyn Renaming<E>>,
+    ) -> Rename<E, TauProof, TickProof> {
+        Rename { p, renaming }
+    }
+}
+
+// Operational semantics for P[[R]]
+//
+//          P -a→ P'
+// 1)  ──────────────────── a R b
+//      P[[R]] -b→ P'[[R]]
+//
+//          P -a→ P'
+// 2)  ──────────────────── a ∈ {τ,✔}
+//      P[[R]] -a→ P'[[R]]
+
+impl<E, TauProof, TickProof> Rename<E, TauProof, TickProof>
+where
+    E: Clone + EventSet + Tau<TauProof> + Tick<TickProof>,
+    TauProof: Clone,
+    TickProof: Clone,
+{
+    /// Applies a renaming function to the visible events in `events`, leaving τ and ✔ alone.
+    fn apply<F>(events: &E, f: F) -> E
+    where
+        F: FnOnce(&E) -> E,
+    {
+        let mut primitives = E::tau();
+        primitives.union(&E::tick());
+        let mut visible = events.clone();
+        visible.subtract(&primitives);
+        let mut result = f(&visible);
+        result.subtract(&primitives);
+        let mut unrenamed = events.clone();
+        unrenamed.intersect(&primitives);
+        result.union(&unrenamed);
+        result
+    }
+
+    pub(crate) fn distribution(&self) -> Option<Distribution<E, TauProof, TickProof>> {
+        let distribution = self.p.distribution()?;
+        Some(map_distribution(distribution, |p| {
+            CSP::shared_rename(p, self.renaming.clone())
+        }))
+    }
+
+    pub(crate) fn initials(&self) -> E {
+        Self::apply(&self.p.initials(), |events| self.renaming.image(events))
+    }
+
+    pub(crate) fn transitions(
+        &self,
+        events: &E,
+    ) -> impl Iterator<Item = (E, CSP<E, TauProof, TickProof>)> + '_ {
+        // P can perform any event that is renamed to one of the requested events.
+        let p_events = Self::apply(events, |events| self.renaming.preimage(events));
+        let events = events.clone();
+        self.p
+            .transitions(&p_events)
+            .filter_map(move |(initials, after)| {
+                // Each event that P performs can be renamed to several events, not all of which
+                // were requested.
+                let mut initials = Self::apply(&initials, |events| self.renaming.image(events));
+                initials.intersect(&events);
+                if initials.is_empty() {
+                    return None;
+                }
+                Some((initials, CSP::shared_rename(after, self.renaming.clone())))
+            })
+    }
+}
+
+#[cfg(test)]
+mod renaming_tests {
+    use super::*;
+
+    use maplit::hashset;
+    use proptest_attr_macro::proptest;
+
+    use crate::csp::CSP;
+    use crate::maximal_traces::maximal_finite_traces;
+    use crate::test_support::NumberedEvent;
+    use crate::test_support::NumberedRenaming;
+    use crate::test_support::TestEvents;
+
+    #[proptest]
+    fn check_renaming_initials(p: CSP<TestEvents, _, _>, renaming: NumberedRenaming) {
+        let process = CSP::rename(p.clone(), renaming.clone());
+        let mut expected = renaming.image(&p.initials());
+        expected.0 = p.initials().0;
+        assert_eq!(process.initials(), expected);
+    }
+
+    #[proptest]
+    fn check_identity_renaming_traces(p: CSP<TestEvents, _, _>) {
+        let process = CSP::rename(p.clone(), NumberedRenaming(vec![]));
+        assert_eq!(maximal_finite_traces(&process), maximal_finite_traces(&p));
+    }
+
+    #[test]
+    fn check_many_to_one_renaming_traces() {
+        let a = NumberedEvent(0);
+        let b = NumberedEvent(1);
+        let c = NumberedEvent(2);
+        let process = CSP::rename(
+            CSP::prefix(a.into(), CSP::prefix(b.into(), CSP::skip())),
+            NumberedRenaming(vec![(a, c), (b, c)]),
+        );
+        assert_eq!(
+            maximal_finite_traces(&process),
+            hashset! {vec![c.into(), c.into(), TestEvents::tick()]}
+        );
+    }
+
+    #[test]
+    fn check_one_to_many_renaming_traces() {
+        let a = NumberedEvent(0);
+        let b = NumberedEvent(1);
+        let c = NumberedEvent(2);
+        let mut bc = TestEvents::from(b);
+        bc.union(&c.into());
+        let process = CSP::rename(
+            CSP::prefix(a.into(), CSP::skip()),
+            NumberedRenaming(vec![(a, b), (a, c)]),
+        );
+        assert_eq!(
+            maximal_finite_traces(&process),
+            hashset! {vec![bc, TestEvents::tick()]}
+        );
+
+        // The environment can also pick just one of the renamed events.
+        let after_b = process.transitions(&b.into()).collect::<Vec<_>>();
+        assert_eq!(after_b.len(), 1);
+        assert_eq!(after_b[0].0, TestEvents::from(b));
+    }
+
+    #[test]
+    fn check_renaming_does_not_rename_primitives() {
+        let process = CSP::rename(
+            CSP::internal_choice(CSP::skip(), CSP::stop()),
+            NumberedRenaming(vec![(NumberedEvent(0), NumberedEvent(1))]),
+        );
+        assert_eq!(process.initials(), TestEvents::tau());
+        assert_eq!(
+            maximal_finite_traces(&process),
+            hashset! {vec![TestEvents::tick()]}
+        );
+    }
+}
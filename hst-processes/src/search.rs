@@ -0,0 +1,111 @@
+// -*- coding: utf-8 -*-
+// ------------------------------------------------------------------------------------------------
+// Copyright © 2020, HST authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License.  You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied.  See the License for the specific language governing permissions and
+// limitations under the License.
+// ------------------------------------------------------------------------------------------------
+
+//! Explores the states of a process in order of the visible traces that reach them.
+
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::hash::Hash;
+
+/// A breadth-first search, where τ steps don't count towards the length of a path.  (This is a
+/// "0-1 BFS".)  That ensures that we visit each state via the shortest visible trace that reaches
+/// it.  Each state is identified by its index in the order that we discovered it.
+pub(crate) struct Search<S, E> {
+    states: Vec<S>,
+    ids: HashMap<S, usize>,
+    distances: Vec<usize>,
+    parents: Vec<Option<(usize, Option<E>)>>,
+    visited: Vec<bool>,
+    queue: VecDeque<usize>,
+}
+
+// Our states are usually processes; see the comment on `CSP` for why they're safe to use as keys.
+#[allow(clippy::mutable_key_type)]
+impl<S, E> Search<S, E>
+where
+    S: Clone + Eq + Hash,
+    E: Clone,
+{
+    pub(crate) fn new(initial: S) -> Search<S, E> {
+        let mut ids = HashMap::new();
+        ids.insert(initial.clone(), 0);
+        Search {
+            states: vec![initial],
+            ids,
+            distances: vec![0],
+            parents: vec![None],
+            visited: vec![false],
+            queue: VecDeque::from(vec![0]),
+        }
+    }
+
+    /// Returns the next state to process, in order of the length of its shortest visible trace.
+    pub(crate) fn next(&mut self) -> Option<usize> {
+        while let Some(index) = self.queue.pop_front() {
+            if !self.visited[index] {
+                self.visited[index] = true;
+                return Some(index);
+            }
+        }
+        None
+    }
+
+    /// Returns the state with the given index.
+    pub(crate) fn state(&self, index: usize) -> &S {
+        &self.states[index]
+    }
+
+    /// Records that state `from` can move to state `to`, performing `events` (or τ, if `events` is
+    /// `None`).  Returns the index of `to`.
+    pub(crate) fn add_edge(&mut self, from: usize, events: Option<E>, to: S) -> usize {
+        let weight = if events.is_some() { 1 } else { 0 };
+        let distance = self.distances[from] + weight;
+        let index = match self.ids.get(&to) {
+            Some(index) => *index,
+            None => {
+                let index = self.states.len();
+                self.states.push(to.clone());
+                self.ids.insert(to, index);
+                self.distances.push(usize::MAX);
+                self.parents.push(None);
+                self.visited.push(false);
+                index
+            }
+        };
+        if distance < self.distances[index] {
+            self.distances[index] = distance;
+            self.parents[index] = Some((from, events));
+            if weight == 0 {
+                self.queue.push_front(index);
+            } else {
+                self.queue.push_back(index);
+            }
+        }
+        index
+    }
+
+    /// Returns the shortest visible trace that leads to a state.
+    pub(crate) fn trace(&self, mut index: usize) -> Vec<E> {
+        let mut trace = Vec::new();
+        while let Some((parent, events)) = &self.parents[index] {
+            if let Some(events) = events {
+                trace.push(events.clone());
+            }
+            index = *parent;
+        }
+        trace.reverse();
+        trace
+    }
+}
@@ -17,6 +17,9 @@
 
 use std::fmt::Debug;
 use std::fmt::Display;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::rc::Rc;
 
 use itertools::Either;
 
@@ -24,6 +27,8 @@ use crate::csp::CSP;
 use crate::event::EventSet;
 use crate::primitives::Tau;
 use crate::primitives::Tick;
+use crate::probabilistic_choice::map_distribution;
+use crate::probabilistic_choice::Distribution;
 
 #[derive(Clone, Eq, Hash, PartialEq)]
 pub struct SequentialComposition<E, TauProof, TickProof> {
@@ -77,6 +82,13 @@ where
     TauProof: Clone,
     TickProof: Clone,
 {
+    pub(crate) fn distribution(&self) -> Option<Distribution<E, TauProof, TickProof>> {
+        let distribution = self.p.distribution()?;
+        Some(map_distribution(distribution, |p| {
+            CSP::sequential_composition(p, self.q.clone())
+        }))
+    }
+
     pub(crate) fn initials(&self) -> E {
         let mut initials = self.p.initials();
         if initials.can_perform_tick() {
@@ -105,9 +117,174 @@ where
         // If P can perform a ✔ leading to P', then P;Q can perform a τ leading to Q.  Note that we
         // don't care what P' is; we just care that it exists.
         let tau_transitions = if events.can_perform_tau() {
-            let tau = E::tau();
-            if !self.p.transitions(&tau).next().is_some() {
-                Either::Left(std::iter::once((tau, self.q.clone())))
+            if self.p.transitions(&E::tick()).next().is_some() {
+                Either::Left(std::iter::once((E::tau(), self.q.clone())))
+            } else {
+                Either::Right(std::iter::empty())
+            }
+        } else {
+            Either::Right(std::iter::empty())
+        };
+
+        other_transitions.chain(tau_transitions)
+    }
+}
+
+//-------------------------------------------------------------------------------------------------
+// Replicated sequential composition
+
+/// A sequential composition of an ordered sequence of processes, `; i:⟨Ps⟩ @ P(i)`.  We store the
+/// sequence once, and keep track of how far along it we are, instead of building a deeply nested
+/// chain of `P ; Q` nodes.
+#[derive(Clone)]
+pub struct ReplicatedSequentialComposition<E, TauProof, TickProof> {
+    current: CSP<E, TauProof, TickProof>,
+    ps: Rc<[CSP<E, TauProof, TickProof>]>,
+    next: usize,
+}
+
+// Comparing the sequences element by element would be expensive for long sequences, so two
+// compositions only have the same sequence if they share the same one.  Every state that a
+// composition can reach shares the original sequence.
+
+impl<E, TauProof, TickProof> PartialEq for ReplicatedSequentialComposition<E, TauProof, TickProof>
+where
+    E: PartialEq,
+    TauProof: PartialEq,
+    TickProof: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.current == other.current && Rc::ptr_eq(&self.ps, &other.ps) && self.next == other.next
+    }
+}
+
+impl<E, TauProof, TickProof> Eq for ReplicatedSequentialComposition<E, TauProof, TickProof>
+where
+    E: Eq,
+    TauProof: Eq,
+    TickProof: Eq,
+{
+}
+
+impl<E, TauProof, TickProof> Hash for ReplicatedSequentialComposition<E, TauProof, TickProof>
+where
+    E: Hash,
+    TauProof: Hash,
+    TickProof: Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.current.hash(state);
+        (self.ps.as_ptr() as usize).hash(state);
+        self.next.hash(state);
+    }
+}
+
+impl<E, TauProof, TickProof> Debug for ReplicatedSequentialComposition<E, TauProof, TickProof>
+where
+    E: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("ReplicatedSequentialComposition")
+            .field("current", &self.current)
+            .field("rest", &&self.ps[self.next..])
+            .finish()
+    }
+}
+
+impl<E, TauProof, TickProof> Display for ReplicatedSequentialComposition<E, TauProof, TickProof>
+where
+    E: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.current)?;
+        for p in &self.ps[self.next..] {
+            write!(f, " ; {}", p)?;
+        }
+        Ok(())
+    }
+}
+
+impl<E, TauProof, TickProof> ReplicatedSequentialComposition<E, TauProof, TickProof> {
+    /// Creates a composition that is currently running `current`, and which will then run each
+    /// process in `ps`, starting at index `next`.  There must be at least one process left to run
+    /// after `current`.
+    pub(crate) fn new(
+        current: CSP<E, TauProof, TickProof>,
+        ps: Rc<[CSP<E, TauProof, TickProof>]>,
+        next: usize,
+    ) -> ReplicatedSequentialComposition<E, TauProof, TickProof> {
+        debug_assert!(next < ps.len());
+        ReplicatedSequentialComposition { current, ps, next }
+    }
+}
+
+// Operational semantics for ; ⟨P₁, …, Pₙ⟩ (while running Pᵢ, with i < n)
+//
+//                  Pᵢ' -a→ Pᵢ''
+// 1)  ──────────────────────────────────────── a ≠ ✔
+//      ; ⟨Pᵢ', Pᵢ₊₁, …⟩ -a→ ; ⟨Pᵢ'', Pᵢ₊₁, …⟩
+//
+//        ∃ Pᵢ'' • Pᵢ' -✔→ Pᵢ''
+// 2)  ──────────────────────────────────────
+//      ; ⟨Pᵢ', Pᵢ₊₁, …⟩ -τ→ ; ⟨Pᵢ₊₁, …⟩
+//
+// Once we reach the last process, we replace the composition with that process, so that its ✔ is
+// visible.
+
+impl<E, TauProof, TickProof> ReplicatedSequentialComposition<E, TauProof, TickProof>
+where
+    E: Clone + EventSet + Tau<TauProof> + Tick<TickProof>,
+    TauProof: Clone,
+    TickProof: Clone,
+{
+    pub(crate) fn distribution(&self) -> Option<Distribution<E, TauProof, TickProof>> {
+        let distribution = self.current.distribution()?;
+        Some(map_distribution(distribution, |current| {
+            CSP::continue_sequential_composition(current, self.ps.clone(), self.next)
+        }))
+    }
+
+    pub(crate) fn initials(&self) -> E {
+        let mut initials = self.current.initials();
+        if initials.can_perform_tick() {
+            initials.subtract(&E::tick());
+            initials.union(&E::tau());
+        }
+        initials
+    }
+
+    pub(crate) fn transitions(
+        &self,
+        events: &E,
+    ) -> impl Iterator<Item = (E, CSP<E, TauProof, TickProof>)> + '_ {
+        let mut events = events.clone();
+
+        // The composition can never perform a ✔; that's always translated into a τ that activates
+        // the next process.
+        events.subtract(&E::tick());
+
+        // If the current process can perform a non-✔ event (including τ), then the composition
+        // can also perform that event, and keeps working through the rest of the sequence.
+        let other_transitions = self
+            .current
+            .transitions(&events)
+            .map(move |(initials, after)| {
+                (
+                    initials,
+                    CSP::continue_sequential_composition(after, self.ps.clone(), self.next),
+                )
+            });
+
+        // If the current process can perform a ✔, then the composition can perform a τ that
+        // moves on to the next process.
+        let tau_transitions = if events.can_perform_tau() {
+            if self.current.transitions(&E::tick()).next().is_some() {
+                let next = CSP::continue_sequential_composition(
+                    self.ps[self.next].clone(),
+                    self.ps.clone(),
+                    self.next + 1,
+                );
+                Either::Left(std::iter::once((E::tau(), next)))
             } else {
                 Either::Right(std::iter::empty())
             }
@@ -126,8 +303,10 @@ mod sequential_composition_tests {
     use proptest_attr_macro::proptest;
 
     use crate::csp::CSP;
+    use crate::event::Here;
     use crate::maximal_traces::maximal_finite_traces;
     use crate::maximal_traces::MaximalTraces;
+    use crate::test_support::NumberedEvent;
     use crate::test_support::TestEvents;
 
     #[proptest]
@@ -148,7 +327,7 @@ mod sequential_composition_tests {
         // For any trace of P, we need to replace a ✔ at the end with all possible traces of Q.
         let mut expected = MaximalTraces::new();
         for mut trace in maximal_finite_traces(&p) {
-            if trace.ends_with(&vec![TestEvents::tick()]) {
+            if trace.ends_with(&[TestEvents::tick()]) {
                 trace.pop();
                 expected.insert(trace.clone());
                 for suffix in maximal_finite_traces(&q) {
@@ -162,4 +341,76 @@ mod sequential_composition_tests {
         }
         assert_eq!(maximal_finite_traces(&process), expected);
     }
+
+    #[test]
+    fn check_sequential_composition_waits_for_tick() {
+        // Q can only start once P has performed a ✔; it doesn't matter whether P can perform a τ.
+        let a = NumberedEvent(0);
+        let b = NumberedEvent(1);
+        let q = CSP::prefix(b.into(), CSP::skip());
+        let process = CSP::sequential_composition(CSP::prefix(a.into(), CSP::skip()), q.clone());
+        assert_eq!(process.initials(), TestEvents::from(a));
+        assert_eq!(process.transitions(&TestEvents::tau()).count(), 0);
+
+        let process = CSP::sequential_composition(CSP::stop(), q.clone());
+        assert_eq!(process.transitions(&TestEvents::tau()).count(), 0);
+
+        let process = CSP::sequential_composition(CSP::skip(), q.clone());
+        let after = process
+            .transitions(&TestEvents::tau())
+            .map(|(_, after)| after)
+            .collect::<Vec<_>>();
+        assert_eq!(after, vec![q]);
+    }
+
+    #[test]
+    fn check_empty_replicated_sequential_composition() {
+        let process = CSP::<TestEvents, Here, Here>::replicated_sequential_composition(vec![]);
+        assert_eq!(process, CSP::skip());
+    }
+
+    #[proptest]
+    fn check_singleton_replicated_sequential_composition(p: CSP<TestEvents, _, _>) {
+        let process = CSP::replicated_sequential_composition(vec![p.clone()]);
+        assert_eq!(maximal_finite_traces(&process), maximal_finite_traces(&p));
+    }
+
+    #[proptest]
+    fn check_replicated_sequential_composition_traces(
+        p: CSP<TestEvents, _, _>,
+        q: CSP<TestEvents, _, _>,
+        r: CSP<TestEvents, _, _>,
+    ) {
+        let process = CSP::replicated_sequential_composition(vec![p.clone(), q.clone(), r.clone()]);
+        let nested = CSP::sequential_composition(p, CSP::sequential_composition(q, r));
+        assert_eq!(process.initials(), nested.initials());
+        assert_eq!(
+            maximal_finite_traces(&process),
+            maximal_finite_traces(&nested)
+        );
+    }
+
+    #[test]
+    fn check_long_replicated_sequential_composition() {
+        // Walk through the process by hand, since a trace this long would overflow the stack of
+        // the recursive trace helpers.
+        let ps = (0..10000).map(|i| CSP::prefix(NumberedEvent(i % 3).into(), CSP::skip()));
+        let mut process = CSP::<TestEvents, Here, Here>::replicated_sequential_composition(ps);
+        let mut trace = Vec::new();
+        loop {
+            let transitions = process.transitions(&process.initials()).collect::<Vec<_>>();
+            if transitions.is_empty() {
+                break;
+            }
+            assert_eq!(transitions.len(), 1);
+            let (initials, after) = transitions.into_iter().next().unwrap();
+            if !initials.can_perform_tau() {
+                trace.push(initials);
+            }
+            process = after;
+        }
+        assert_eq!(trace.len(), 10001);
+        assert_eq!(trace[9999], NumberedEvent(0).into());
+        assert_eq!(trace[10000], TestEvents::tick());
+    }
 }
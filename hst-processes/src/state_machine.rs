@@ -0,0 +1,203 @@
+// -*- coding: utf-8 -*-
+// ------------------------------------------------------------------------------------------------
+// Copyright © 2020, HST authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License.  You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied.  See the License for the specific language governing permissions and
+// limitations under the License.
+// ------------------------------------------------------------------------------------------------
+
+//! Defines processes whose behavior is given by a state machine written in Rust.
+
+use std::fmt::Debug;
+use std::fmt::Display;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::rc::Rc;
+
+use crate::csp::CSP;
+use crate::event::EventSet;
+use crate::operator::Operator;
+
+/// The step function of a state machine.  Given the current state and a set of events, it returns
+/// the transitions that the machine can perform from that state.  Each transition consists of a
+/// subset of the events that were passed in, and the state that those events lead to.
+pub type StepFunction<S, E> = fn(&S, &E) -> Vec<(E, S)>;
+
+/// A process whose behavior is defined by a state machine.
+pub(crate) struct StateMachine<S, E> {
+    state: S,
+    step: Rc<StepFunction<S, E>>,
+}
+
+impl<S, E> StateMachine<S, E> {
+    pub(crate) fn new(state: S, step: StepFunction<S, E>) -> StateMachine<S, E> {
+        StateMachine {
+            state,
+            step: Rc::new(step),
+        }
+    }
+}
+
+// Function pointers aren't guaranteed to have unique or stable addresses, so we can't compare
+// them directly.  Instead, every state of a machine shares the same copy of its step function,
+// and two machines are only the same if they're in the same state _and_ share that copy.
+
+impl<S, E> PartialEq for StateMachine<S, E>
+where
+    S: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.state == other.state && Rc::ptr_eq(&self.step, &other.step)
+    }
+}
+
+impl<S, E> Eq for StateMachine<S, E> where S: Eq {}
+
+impl<S, E> Hash for StateMachine<S, E>
+where
+    S: Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.state.hash(state);
+        (Rc::as_ptr(&self.step) as usize).hash(state);
+    }
+}
+
+impl<S, E> Debug for StateMachine<S, E>
+where
+    S: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "StateMachine({:?})", self.state)
+    }
+}
+
+impl<S, E> Display for StateMachine<S, E>
+where
+    S: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:?}", self.state)
+    }
+}
+
+// Operational semantics for a state machine M in state s
+//
+//      (a, s') ∈ step(s)
+// 1) ─────────────────────
+//       M(s) -a→ M(s')
+
+impl<S, E, TauProof, TickProof> Operator<E, TauProof, TickProof> for StateMachine<S, E>
+where
+    S: Clone + Debug + Eq + Hash + 'static,
+    E: Clone + EventSet + 'static,
+    TauProof: 'static,
+    TickProof: 'static,
+{
+    fn initials(&self) -> E {
+        let mut initials = E::empty();
+        for (events, _) in (self.step)(&self.state, &E::universe()) {
+            initials.union(&events);
+        }
+        initials
+    }
+
+    fn transitions<'a>(
+        &'a self,
+        events: &E,
+    ) -> Box<dyn Iterator<Item = (E, CSP<E, TauProof, TickProof>)> + 'a> {
+        // Make sure that a badly behaved step function can't produce events that we didn't ask
+        // for.
+        let events = events.clone();
+        Box::new((self.step)(&self.state, &events).into_iter().filter_map(
+            move |(mut initials, after)| {
+                initials.intersect(&events);
+                if initials.is_empty() {
+                    None
+                } else {
+                    let after = StateMachine {
+                        state: after,
+                        step: self.step.clone(),
+                    };
+                    Some((initials, CSP::operator(after)))
+                }
+            },
+        ))
+    }
+}
+
+#[cfg(test)]
+mod state_machine_tests {
+    use maplit::hashset;
+
+    use crate::csp::CSP;
+    use crate::event::EventSet;
+    use crate::maximal_traces::maximal_finite_traces;
+    use crate::primitives::Tick;
+    use crate::test_support::NumberedEvent;
+    use crate::test_support::TestEvents;
+
+    /// Counts down from the initial state, performing an event for each number along the way, and
+    /// then terminates.  The state is `None` once the machine has terminated.
+    fn countdown(state: &Option<u16>, events: &TestEvents) -> Vec<(TestEvents, Option<u16>)> {
+        let (mut initials, next) = match *state {
+            None => return vec![],
+            Some(0) => (TestEvents::tick(), None),
+            Some(n) => (TestEvents::from(NumberedEvent(n)), Some(n - 1)),
+        };
+        initials.intersect(events);
+        if initials.is_empty() {
+            return vec![];
+        }
+        vec![(initials, next)]
+    }
+
+    /// Alternates between two events forever.
+    fn toggle(state: &bool, events: &TestEvents) -> Vec<(TestEvents, bool)> {
+        let mut initials = TestEvents::from(NumberedEvent(*state as u16));
+        initials.intersect(events);
+        if initials.is_empty() {
+            return vec![];
+        }
+        vec![(initials, !state)]
+    }
+
+    #[test]
+    fn check_state_machine_initials() {
+        let process = CSP::<_, _, _>::from_state_machine(Some(2), countdown);
+        assert_eq!(process.initials(), NumberedEvent(2).into());
+    }
+
+    #[test]
+    fn check_state_machine_traces() {
+        let process = CSP::<_, _, _>::from_state_machine(Some(2), countdown);
+        assert_eq!(
+            maximal_finite_traces(&process),
+            hashset! {vec![
+                NumberedEvent(2).into(),
+                NumberedEvent(1).into(),
+                TestEvents::tick(),
+            ]}
+        );
+    }
+
+    #[test]
+    fn check_state_machine_loops_back_to_itself() {
+        let process = CSP::<_, _, _>::from_state_machine(false, toggle);
+        let (_, after) = process.transitions(&TestEvents::universe()).next().unwrap();
+        assert_ne!(after, process);
+        let (_, after) = after.transitions(&TestEvents::universe()).next().unwrap();
+        assert_eq!(after, process);
+        assert_eq!(
+            maximal_finite_traces(&process),
+            hashset! {vec![NumberedEvent(0).into(), NumberedEvent(1).into()]}
+        );
+    }
+}
@@ -28,6 +28,9 @@ use proptest::strategy::Strategy;
 use crate::event::DisjointSum;
 use crate::event::EventSet;
 use crate::primitives::PrimitiveEvents;
+use crate::primitives::Tock;
+use crate::primitives::TockEvents;
+use crate::renaming::Renaming;
 
 /// An event that is identified by a number.  Makes it easy to construct distinct events in
 /// test cases.
@@ -73,7 +76,7 @@ impl Arbitrary for NumberedEvent {
 fn can_display_events() {
     assert_eq!(NumberedEvent(0).to_string(), "E₀");
     assert_eq!(NumberedEvent(10).to_string(), "E₁₀");
-    assert_eq!(NumberedEvent(01234).to_string(), "E₁₂₃₄");
+    assert_eq!(NumberedEvent(1234).to_string(), "E₁₂₃₄");
 }
 
 #[derive(Clone, Eq, Hash, PartialEq)]
@@ -305,6 +308,95 @@ impl From<NumberedEvents> for TestEvents {
     }
 }
 
+/// Returns the set containing a single numbered event.
+pub fn event(number: u16) -> TestEvents {
+    TestEvents::from(NumberedEvent(number))
+}
+
+/// An event type that is useful in test cases that need discrete time.  It can be a
+/// NumberedEvent, tock, or any of the built-in event types.
+pub type TimedTestEvents = DisjointSum<PrimitiveEvents, DisjointSum<TockEvents, NumberedEvents>>;
+
+impl From<NumberedEvent> for TimedTestEvents {
+    fn from(event: NumberedEvent) -> TimedTestEvents {
+        TimedTestEvents::from_b(DisjointSum::from_b(event.into()))
+    }
+}
+
+impl From<NumberedEvents> for TimedTestEvents {
+    fn from(events: NumberedEvents) -> TimedTestEvents {
+        TimedTestEvents::from_b(DisjointSum::from_b(events))
+    }
+}
+
+/// Returns a trace that lets `n` units of time pass.
+pub fn tocks(n: usize) -> Vec<TimedTestEvents> {
+    vec![TimedTestEvents::tock(); n]
+}
+
+/// A renaming relation between individual numbered events.  Any numbered event that doesn't
+/// appear on the left-hand side of any pair is left unchanged.
+#[derive(Clone, Debug)]
+pub struct NumberedRenaming(pub Vec<(NumberedEvent, NumberedEvent)>);
+
+impl NumberedRenaming {
+    fn domain(&self) -> NumberedEvents {
+        let mut domain = NumberedEvents::empty();
+        for (from, _) in &self.0 {
+            domain.add(*from);
+        }
+        domain
+    }
+}
+
+impl Display for NumberedRenaming {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("{")?;
+        for (index, (from, to)) in self.0.iter().enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{} ↦ {}", from, to)?;
+        }
+        f.write_str("}")
+    }
+}
+
+impl Renaming<TestEvents> for NumberedRenaming {
+    fn image(&self, events: &TestEvents) -> TestEvents {
+        let mut image = events.1.clone();
+        image.subtract(&self.domain());
+        for (from, to) in &self.0 {
+            if events.1.contains(*from) {
+                image.add(*to);
+            }
+        }
+        DisjointSum(events.0.clone(), image)
+    }
+
+    fn preimage(&self, events: &TestEvents) -> TestEvents {
+        let mut preimage = events.1.clone();
+        preimage.subtract(&self.domain());
+        for (from, to) in &self.0 {
+            if events.1.contains(*to) {
+                preimage.add(*from);
+            }
+        }
+        DisjointSum(events.0.clone(), preimage)
+    }
+}
+
+impl Arbitrary for NumberedRenaming {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<NumberedRenaming>;
+
+    fn arbitrary_with(_args: ()) -> Self::Strategy {
+        vec(any::<(NumberedEvent, NumberedEvent)>(), 0..16)
+            .prop_map(NumberedRenaming)
+            .boxed()
+    }
+}
+
 /// A proptest helper type that generates a non-empty vector of values.
 #[derive(Clone, Debug)]
 pub struct NonemptyVec<T> {
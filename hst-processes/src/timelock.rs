@@ -0,0 +1,177 @@
+// -*- coding: utf-8 -*-
+// ------------------------------------------------------------------------------------------------
+// Copyright © 2020, HST authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License.  You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied.  See the License for the specific language governing permissions and
+// limitations under the License.
+// ------------------------------------------------------------------------------------------------
+//! Checks whether time can always pass in a tock-CSP process.
+//!
+//! In tock-CSP, the passage of time is modeled by the tock event.  A process _timelocks_ if it
+//! reaches a state where time can never pass again: no matter which events the environment lets
+//! it perform, it will never be able to perform another tock.  (A process that has terminated
+//! doesn't timelock, since it places no more constraints on time.)  This is how we detect that a
+//! timed process has missed a deadline — see [`CSP::deadline`][crate::CSP::deadline].
+
+use std::hash::Hash;
+
+use crate::csp::CSP;
+use crate::event::EventSet;
+use crate::primitives::Tau;
+use crate::primitives::Tick;
+use crate::primitives::Tock;
+use crate::search::Search;
+
+/// Checks whether a process is timelock free — that is, whether after every trace, the process
+/// can eventually perform a tock (or terminate).  If not, returns the shortest trace that leads to
+/// a state where time can never pass again.
+pub fn is_timelock_free<E, TauProof, TickProof, TockProof>(
+    process: &CSP<E, TauProof, TickProof>,
+) -> Result<(), Vec<E>>
+where
+    E: Clone + Eq + EventSet + Hash + Tau<TauProof> + Tick<TickProof> + Tock<TockProof>,
+    TauProof: Clone + Eq + Hash,
+    TickProof: Clone + Eq + Hash,
+{
+    // First explore every reachable state, keeping track of which states can let time pass (or
+    // terminate) immediately, and which states lead to which.
+    let mut search = Search::new(process.clone());
+    let mut order = Vec::new();
+    let mut progressing = Vec::new();
+    let mut edges = Vec::new();
+    while let Some(index) = search.next() {
+        order.push(index);
+        let process = search.state(index).clone();
+        let mut initials = process.initials();
+        if initials.can_perform_tock() || initials.can_perform_tick() {
+            progressing.push(index);
+        }
+
+        // Anything that happens after a process terminates doesn't count.
+        initials.subtract(&E::tick());
+        for (mut initials, after) in process.transitions(&initials) {
+            if initials.can_perform_tau() {
+                initials.subtract(&E::tau());
+                edges.push((index, search.add_edge(index, None, after.clone())));
+            }
+            if !initials.is_empty() {
+                edges.push((index, search.add_edge(index, Some(initials), after)));
+            }
+        }
+    }
+
+    // Then work backwards to find every state that can eventually let time pass.  (The search
+    // processes every state that it discovers, so `order` tells us how many states there are.)
+    let mut can_progress = vec![false; order.len()];
+    let mut predecessors = vec![Vec::new(); order.len()];
+    for (from, to) in edges {
+        predecessors[to].push(from);
+    }
+    for index in &progressing {
+        can_progress[*index] = true;
+    }
+    while let Some(index) = progressing.pop() {
+        for predecessor in &predecessors[index] {
+            if !can_progress[*predecessor] {
+                can_progress[*predecessor] = true;
+                progressing.push(*predecessor);
+            }
+        }
+    }
+
+    // We visited the states in order of the length of the shortest trace that reaches them, so
+    // the first timelocked state that we visited has the shortest counterexample.
+    match order.into_iter().find(|index| !can_progress[*index]) {
+        Some(index) => Err(search.trace(index)),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod timelock_tests {
+    use super::*;
+
+    use crate::recursion::RecursionScope;
+    use crate::test_support::tocks;
+    use crate::test_support::NumberedEvent;
+    use crate::test_support::TimedTestEvents;
+
+    #[test]
+    fn check_wait_is_timelock_free() {
+        let process = CSP::<TimedTestEvents, _, _>::wait(3);
+        assert_eq!(is_timelock_free(&process), Ok(()));
+    }
+
+    #[test]
+    fn check_stop_timelocks() {
+        let process = CSP::<TimedTestEvents, _, _>::stop();
+        assert_eq!(is_timelock_free(&process), Err(vec![]));
+    }
+
+    #[test]
+    fn check_response_within_deadline() {
+        // WAIT 2 ; resp → Skip responds within 3 tocks.
+        let resp = TimedTestEvents::from(NumberedEvent(0));
+        let p = CSP::sequential_composition(CSP::wait(2), CSP::prefix(resp, CSP::skip()));
+        assert_eq!(is_timelock_free(&CSP::deadline(p, 3)), Ok(()));
+    }
+
+    #[test]
+    fn check_response_misses_deadline() {
+        // WAIT 4 ; resp → Skip does not respond within 3 tocks, so time stops after the third.
+        let resp = TimedTestEvents::from(NumberedEvent(0));
+        let p = CSP::sequential_composition(CSP::wait(4), CSP::prefix(resp, CSP::skip()));
+        assert_eq!(is_timelock_free(&CSP::deadline(p, 3)), Err(tocks(3)));
+    }
+
+    #[test]
+    fn check_delayable_response_within_deadline() {
+        // P = resp → Skip □ tock → P can respond at any time, so the deadline forces it to
+        // respond within 3 tocks, without timelocking.
+        let resp = TimedTestEvents::from(NumberedEvent(0));
+        let scope = RecursionScope::new();
+        let p = scope.create_process("P");
+        scope.define(
+            p,
+            CSP::external_choice(
+                CSP::prefix(resp, CSP::skip()),
+                CSP::prefix(TimedTestEvents::tock(), scope.recurse(p)),
+            ),
+        );
+        assert_eq!(
+            is_timelock_free(&CSP::deadline(scope.recurse(p), 3)),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn check_zeno_process_timelocks() {
+        // P = a → P can never let time pass, even though it never deadlocks.
+        let a = TimedTestEvents::from(NumberedEvent(0));
+        let scope = RecursionScope::new();
+        let p = scope.create_process("P");
+        scope.define(p, CSP::prefix(a.clone(), scope.recurse(p)));
+        let process = CSP::prefix(TimedTestEvents::tock(), scope.recurse(p));
+        assert_eq!(is_timelock_free(&process), Err(tocks(1)));
+    }
+
+    #[test]
+    fn check_urgent_events_do_not_timelock() {
+        // (a → Skip) □ (tock → b → Skip), where a is urgent, can't let time pass immediately, but
+        // can terminate.
+        let a = TimedTestEvents::from(NumberedEvent(0));
+        let b = TimedTestEvents::from(NumberedEvent(1));
+        let p = CSP::external_choice(
+            CSP::prefix(a.clone(), CSP::skip()),
+            CSP::prefix(TimedTestEvents::tock(), CSP::prefix(b, CSP::skip())),
+        );
+        assert_eq!(is_timelock_free(&CSP::urgent(p, a)), Ok(()));
+    }
+}
@@ -0,0 +1,165 @@
+// -*- coding: utf-8 -*-
+// ------------------------------------------------------------------------------------------------
+// Copyright © 2020, HST authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License.  You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied.  See the License for the specific language governing permissions and
+// limitations under the License.
+// ------------------------------------------------------------------------------------------------
+
+//! Defines the timeout (sliding choice, `▷`) operator.
+
+use std::fmt::Debug;
+use std::fmt::Display;
+
+use itertools::Either;
+
+use crate::csp::CSP;
+use crate::event::EventSet;
+use crate::external_choice::split_choice_transition;
+use crate::primitives::Tau;
+use crate::primitives::Tick;
+use crate::probabilistic_choice::map_distribution;
+use crate::probabilistic_choice::Distribution;
+
+#[derive(Clone, Eq, Hash, PartialEq)]
+pub struct Timeout<E, TauProof, TickProof> {
+    p: CSP<E, TauProof, TickProof>,
+    q: CSP<E, TauProof, TickProof>,
+}
+
+impl<E, TauProof, TickProof> Debug for Timeout<E, TauProof, TickProof>
+where
+    E: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("Timeout")
+            .field("p", &self.p)
+            .field("q", &self.q)
+            .finish()
+    }
+}
+
+impl<E, TauProof, TickProof> Display for Timeout<E, TauProof, TickProof>
+where
+    E: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} ▷ {}", self.p, self.q)
+    }
+}
+
+impl<E, TauProof, TickProof> Timeout<E, TauProof, TickProof> {
+    pub(crate) fn new(
+        p: CSP<E, TauProof, TickProof>,
+        q: CSP<E, TauProof, TickProof>,
+    ) -> Timeout<E, TauProof, TickProof> {
+        Timeout { p, q }
+    }
+}
+
+// Operational semantics for P ▷ Q
+//
+//          P -τ→ P'
+//  1)  ──────────────────
+//       P ▷ Q -τ→ P' ▷ Q
+//
+//        P -a→ P'
+//  2)  ───────────── a ≠ τ
+//       P ▷ Q -a→ P'
+//
+//  3)  ─────────────
+//       P ▷ Q -τ→ Q
+
+impl<E, TauProof, TickProof> Timeout<E, TauProof, TickProof>
+where
+    E: Clone + EventSet + Tau<TauProof> + Tick<TickProof>,
+    TauProof: Clone,
+    TickProof: Clone,
+{
+    pub(crate) fn distribution(&self) -> Option<Distribution<E, TauProof, TickProof>> {
+        let distribution = self.p.distribution()?;
+        Some(map_distribution(distribution, |p| {
+            CSP::timeout(p, self.q.clone())
+        }))
+    }
+
+    pub(crate) fn initials(&self) -> E {
+        let mut initials = self.p.initials();
+        initials.union(&E::tau());
+        initials
+    }
+
+    pub(crate) fn transitions(
+        &self,
+        events: &E,
+    ) -> impl Iterator<Item = (E, CSP<E, TauProof, TickProof>)> + '_ {
+        // P's τ events _do not_ resolve the choice; any other event does.
+        let p_transitions = self.p.transitions(events).flat_map(move |transition| {
+            split_choice_transition(transition, |after| CSP::timeout(after, self.q.clone()))
+        });
+
+        // The timeout can also fire at any point, resolving the choice in favor of Q.
+        let timeout_transitions = if events.can_perform_tau() {
+            Either::Left(std::iter::once((E::tau(), self.q.clone())))
+        } else {
+            Either::Right(std::iter::empty())
+        };
+
+        p_transitions.chain(timeout_transitions)
+    }
+}
+
+#[cfg(test)]
+mod timeout_tests {
+    use super::*;
+
+    use maplit::hashset;
+    use proptest_attr_macro::proptest;
+
+    use crate::csp::CSP;
+    use crate::maximal_traces::maximal_finite_traces;
+    use crate::test_support::NumberedEvent;
+    use crate::test_support::TestEvents;
+
+    #[proptest]
+    fn check_timeout_initials(p: CSP<TestEvents, _, _>, q: CSP<TestEvents, _, _>) {
+        let process = CSP::timeout(p.clone(), q);
+        let mut expected = p.initials();
+        expected.union(&TestEvents::tau());
+        assert_eq!(process.initials(), expected);
+    }
+
+    #[proptest]
+    fn check_timeout_traces(p: CSP<TestEvents, _, _>, q: CSP<TestEvents, _, _>) {
+        let process = CSP::timeout(p.clone(), q.clone());
+        assert_eq!(
+            maximal_finite_traces(&process),
+            maximal_finite_traces(&p) + maximal_finite_traces(&q)
+        );
+    }
+
+    #[test]
+    fn check_timeout_transitions() {
+        let a = TestEvents::from(NumberedEvent(0));
+        let b = TestEvents::from(NumberedEvent(1));
+        let p = CSP::prefix(a.clone(), CSP::stop());
+        let q = CSP::prefix(b.clone(), CSP::stop());
+        let process = CSP::timeout(p, q.clone());
+        let mut events = a.clone();
+        events.union(&b);
+        events.union(&TestEvents::tau());
+        let transitions = process.transitions(&events).collect::<Vec<_>>();
+        assert_eq!(
+            transitions,
+            vec![(a.clone(), CSP::stop()), (TestEvents::tau(), q)]
+        );
+        assert_eq!(maximal_finite_traces(&process), hashset! {vec![a], vec![b]});
+    }
+}